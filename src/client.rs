@@ -1,16 +1,224 @@
 use anyhow::{Context, Result, bail};
+use reqwest::StatusCode;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
-use std::path::PathBuf;
-use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{Instrument, debug, debug_span};
 
 use crate::types::*;
 
-const BASE_API_URL: &str = "https://api.anthropic.com";
+const DEFAULT_BASE_API_URL: &str = "https://api.anthropic.com";
+const BASE_URL_ENV: &str = "CTA_API_BASE_URL";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 const ANTHROPIC_BETA: &str = "ccr-byoc-2025-07-29";
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const TIMEOUT_ENV: &str = "CTA_TIMEOUT";
+const CONNECT_TIMEOUT_ENV: &str = "CTA_CONNECT_TIMEOUT";
 const MAX_ERROR_BODY_LEN: usize = 500;
+const ORG_CACHE_FILE: &str = ".teleport-analyzer-org.json";
+const ORG_CACHE_TTL_SECS: u64 = 60 * 60;
+const SESSION_CACHE_DIR: &str = ".teleport-analyzer-sessions";
+const ACCESS_TOKEN_ENV: &str = "CLAUDE_ACCESS_TOKEN";
+/// 9999-12-31T23:59:59Z in milliseconds; an environment token carries no expiry.
+const ENV_TOKEN_EXPIRES_AT: u64 = 253_402_300_799_000;
+/// Page cap for `get_events`, in case the API never stops reporting `has_more`.
+const DEFAULT_MAX_PAGES: usize = 10_000;
+/// Largest `--page-size` accepted.
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Returns the API base URL: `override_url` (from `CTA_API_BASE_URL`) if set
+/// and non-blank, else production. Trailing slashes are dropped.
+fn resolve_base_url(override_url: Option<&str>) -> String {
+    override_url
+        .map(|u| u.trim().trim_end_matches('/'))
+        .filter(|u| !u.is_empty())
+        .unwrap_or(DEFAULT_BASE_API_URL)
+        .to_string()
+}
+
+/// The API base URL clients use: `CTA_API_BASE_URL` if set, otherwise the
+/// production API.
+pub fn base_url() -> String {
+    resolve_base_url(std::env::var(BASE_URL_ENV).ok().as_deref())
+}
+
+/// HTTP timeouts applied to every client; `None` disables that timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Whole request, from sending to reading the last byte of the body.
+    pub request: Option<Duration>,
+    pub connect: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            request: Some(REQUEST_TIMEOUT),
+            connect: Some(CONNECT_TIMEOUT),
+        }
+    }
+}
+
+impl Timeouts {
+    /// Resolves timeouts from flags (in seconds), falling back to
+    /// `CTA_TIMEOUT` / `CTA_CONNECT_TIMEOUT`, then the defaults. 0 disables.
+    pub fn resolve(request_secs: Option<u64>, connect_secs: Option<u64>) -> Result<Self> {
+        let env = |name| std::env::var(name).ok();
+        Ok(Self {
+            request: resolve_timeout(request_secs, TIMEOUT_ENV, env(TIMEOUT_ENV), REQUEST_TIMEOUT)?,
+            connect: resolve_timeout(
+                connect_secs,
+                CONNECT_TIMEOUT_ENV,
+                env(CONNECT_TIMEOUT_ENV),
+                CONNECT_TIMEOUT,
+            )?,
+        })
+    }
+}
+
+fn resolve_timeout(
+    flag_secs: Option<u64>,
+    env_name: &str,
+    env_value: Option<String>,
+    default: Duration,
+) -> Result<Option<Duration>> {
+    let secs = match (flag_secs, env_value) {
+        (Some(secs), _) => secs,
+        (None, Some(value)) => value.trim().parse().with_context(|| {
+            format!("Invalid {env_name} value '{value}': expected whole seconds")
+        })?,
+        (None, None) => return Ok(Some(default)),
+    };
+    Ok((secs > 0).then(|| Duration::from_secs(secs)))
+}
+
+/// How [`ApiClient::new`] builds a client. The default uses the 30s request /
+/// 10s connect timeouts, lets the API pick the page size, and shows no progress.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClientOptions {
+    pub timeouts: Timeouts,
+    /// `limit` sent with each events page; `None` leaves it to the API.
+    pub page_size: Option<u32>,
+    /// Show a spinner on stderr while connecting (only when it is a terminal)
+    /// and the pagination progress line.
+    pub progress: bool,
+}
+
+/// A non-success HTTP response, classified by status so callers can react
+/// to specific failures (e.g. prompting a re-login on `Unauthorized`).
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    /// The body is kept for debugging but left out of the message: it rarely
+    /// says more than the status, and the fix is always a fresh token.
+    /// There is no refresh flow, so 401s are not retried.
+    #[error("{context}: the OAuth token is invalid or expired ({status})")]
+    Unauthorized {
+        context: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[error("{context}: {status} - {body}")]
+    NotFound {
+        context: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[error("{context}: {status} - {body}")]
+    RateLimited {
+        context: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[error("{context}: {status} - {body}")]
+    Server {
+        context: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[error("{context}: {status} - {body}")]
+    Other {
+        context: String,
+        status: StatusCode,
+        body: String,
+    },
+}
+
+impl ApiError {
+    pub fn from_status(context: impl Into<String>, status: StatusCode, body: &str) -> Self {
+        let context = context.into();
+        let body = truncate_error_body(body).to_string();
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized {
+                context,
+                status,
+                body,
+            },
+            StatusCode::NOT_FOUND => Self::NotFound {
+                context,
+                status,
+                body,
+            },
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                context,
+                status,
+                body,
+            },
+            s if s.is_server_error() => Self::Server {
+                context,
+                status,
+                body,
+            },
+            _ => Self::Other {
+                context,
+                status,
+                body,
+            },
+        }
+    }
+}
+
+/// Passes successful responses through; otherwise reads the body and returns
+/// the matching [`ApiError`]. A 401 may mean the cached org UUID is stale
+/// (e.g. the user switched orgs), so it also drops that cache.
+async fn check_response(
+    resp: reqwest::Response,
+    context: impl Into<String>,
+    org_cache: Option<&Path>,
+) -> Result<reqwest::Response, ApiError> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    if status == StatusCode::UNAUTHORIZED
+        && let Some(path) = org_cache
+    {
+        invalidate_org_cache(path);
+    }
+    let body = resp.text().await.unwrap_or_default();
+    Err(ApiError::from_status(context, status, &body))
+}
+
+/// Sends `request`, logging its URL, then the status and latency, at debug
+/// level (`-v`). Headers are never logged, so the token stays out of logs.
+async fn send_logged(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    debug!(method = %request.method(), url = %request.url(), "sending request");
+    let started = Instant::now();
+    let result = client.execute(request).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(resp) => debug!(status = %resp.status(), elapsed_ms, "received response"),
+        Err(err) => debug!(error = %err, elapsed_ms, "request failed"),
+    }
+    result
+}
 
 fn truncate_error_body(body: &str) -> &str {
     if body.len() <= MAX_ERROR_BODY_LEN {
@@ -20,6 +228,163 @@ fn truncate_error_body(body: &str) -> &str {
     }
 }
 
+/// Builds the single-line progress message shown while paginating events.
+///
+/// The API reports no total, so the remaining estimate is only available when
+/// a `max_events` cap is set; otherwise `has_more` just signals more pages.
+fn format_fetch_progress(
+    fetched: usize,
+    elapsed: Duration,
+    has_more: bool,
+    max_events: usize,
+) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 {
+        (fetched as f64 / secs).round() as u64
+    } else {
+        0
+    };
+    let mut msg = format!("Fetched {fetched} events ({rate}/s");
+    if max_events > 0 && has_more {
+        msg.push_str(&format!(
+            ", ~{} remaining",
+            max_events.saturating_sub(fetched)
+        ));
+    } else if has_more {
+        msg.push_str(", more pending");
+    }
+    msg.push_str(")...");
+    msg
+}
+
+/// One line of `--raw` pagination metadata; missing cursors show as `-`.
+fn format_page_cursors(page_no: usize, page: &EventsResponse) -> String {
+    format!(
+        "page {page_no}: {} events, first_id={} last_id={} has_more={}",
+        page.data.len(),
+        page.first_id.as_deref().unwrap_or("-"),
+        page.last_id.as_deref().unwrap_or("-"),
+        page.has_more
+            .map_or_else(|| "-".to_string(), |more| more.to_string()),
+    )
+}
+
+/// Spinner frames for [`Spinner`].
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Animated status line on stderr for steps with no other output, such as
+/// loading credentials and resolving the org UUID. Cleared when dropped.
+struct Spinner {
+    message: Arc<Mutex<String>>,
+    stop: Option<std::sync::mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Spinner {
+    /// Starts animating `message`; does nothing unless `enabled` and stderr
+    /// is a terminal.
+    fn start(message: &str, enabled: bool) -> Self {
+        let message = Arc::new(Mutex::new(message.to_string()));
+        if !enabled || !std::io::stderr().is_terminal() {
+            return Self {
+                message,
+                stop: None,
+                thread: None,
+            };
+        }
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let shown = Arc::clone(&message);
+        let thread = std::thread::spawn(move || {
+            for frame in SPINNER_FRAMES.iter().cycle() {
+                let text = shown.lock().map(|m| m.clone()).unwrap_or_default();
+                // Clear to end of line in case the message got shorter.
+                eprint!("\r{frame} {text}\x1b[K");
+                let _ = std::io::stderr().flush();
+                // Dropping the sender disconnects the channel and ends the loop.
+                if stopped.recv_timeout(SPINNER_INTERVAL)
+                    != Err(std::sync::mpsc::RecvTimeoutError::Timeout)
+                {
+                    break;
+                }
+            }
+            eprint!("\r\x1b[2K");
+        });
+        Self {
+            message,
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Whether the spinner is drawing, i.e. it was enabled on a terminal.
+    fn is_active(&self) -> bool {
+        self.thread.is_some()
+    }
+
+    /// Replaces the text shown next to the spinner from the next frame on.
+    fn set_message(&self, message: String) {
+        if let Ok(mut current) = self.message.lock() {
+            *current = message;
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The running event count while paginating. On a terminal it animates as a
+/// spinner between pages; otherwise the line is redrawn once per page.
+struct FetchProgress {
+    enabled: bool,
+    spinner: Spinner,
+    last: String,
+}
+
+impl FetchProgress {
+    fn start(enabled: bool) -> Self {
+        Self {
+            enabled,
+            spinner: Spinner::start("Fetching events...", enabled),
+            last: String::new(),
+        }
+    }
+
+    fn update(&mut self, line: String) {
+        if !self.enabled {
+            return;
+        }
+        if self.spinner.is_active() {
+            self.spinner.set_message(line.clone());
+        } else {
+            // Trailing spaces clear leftovers from a longer previous line.
+            eprint!("\r  {line}    ");
+        }
+        self.last = line;
+    }
+
+    /// Ends the progress line, leaving the last count on screen.
+    fn finish(self) {
+        if !self.enabled {
+            return;
+        }
+        let active = self.spinner.is_active();
+        drop(self.spinner);
+        if active && !self.last.is_empty() {
+            eprintln!("  {}", self.last);
+        } else if !active {
+            eprintln!();
+        }
+    }
+}
+
+/// Checks that `id` looks like `session_01...` before it is used in a URL.
 pub fn validate_session_id(id: &str) -> Result<()> {
     if !id.starts_with("session_")
         || id.len() < 16
@@ -33,32 +398,134 @@ pub fn validate_session_id(id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Where the OAuth credentials were loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// The `CLAUDE_ACCESS_TOKEN` environment variable.
+    Env,
+    Keychain,
+    File(PathBuf),
+}
+
+impl std::fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env => write!(f, "{ACCESS_TOKEN_ENV} environment variable"),
+            Self::Keychain => write!(f, "macOS Keychain"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Which events `get_events_query` fetches.
+#[derive(Debug, Default, Clone)]
+pub struct EventQuery {
+    /// Start after this event UUID instead of at the beginning; sent as the
+    /// `after_id` cursor, so earlier pages are never fetched.
+    pub after_id: Option<String>,
+    /// Stop before this event UUID. The API has no such bound, so pagination
+    /// simply ends once the event is seen.
+    pub before_id: Option<String>,
+    /// Cap on returned events; 0 means all.
+    pub max_events: usize,
+    /// If a page after the first fails, warn and return the events fetched
+    /// so far instead of failing.
+    pub best_effort: bool,
+    /// Print each page's `first_id`, `last_id`, and `has_more` to stderr.
+    pub log_cursors: bool,
+}
+
+/// Authenticated client for the sessions API.
 pub struct ApiClient {
     client: reqwest::Client,
+    base_url: String,
     access_token: String,
     org_uuid: String,
+    /// Org UUID cache to clear on a 401; `None` when the client didn't use it.
+    org_cache: Option<PathBuf>,
+    progress: bool,
+    max_pages: usize,
+    /// `limit` for each events page; `None` leaves it to the API.
+    page_size: Option<u32>,
 }
 
 impl ApiClient {
-    pub async fn new() -> Result<Self> {
-        let creds = load_credentials()?;
-        let access_token = creds.claude_ai_oauth.access_token;
+    /// Loads OAuth credentials and resolves the organization UUID, using
+    /// `CTA_API_BASE_URL` as the API base if set.
+    pub async fn new(opts: ClientOptions) -> Result<Self> {
+        // Before the spinner, so credential warnings don't land on its line.
+        let (creds, _) = load_credentials()?;
+        Self::with_credentials(&creds, opts).await
+    }
 
-        let client = reqwest::Client::builder()
-            .timeout(REQUEST_TIMEOUT)
-            .connect_timeout(CONNECT_TIMEOUT)
-            .build()
-            .context("Failed to build HTTP client")?;
+    /// Like [`ApiClient::new`], but with credentials the caller already loaded
+    /// through [`load_credentials`].
+    pub async fn with_credentials(creds: &OAuthCredentials, opts: ClientOptions) -> Result<Self> {
+        let _spinner = Spinner::start("Connecting to the API...", opts.progress);
+        let access_token = creds.claude_ai_oauth.access_token.clone();
+        let client = build_http_client(opts.timeouts)?;
+        let base_url = base_url();
 
-        let org_uuid = fetch_org_uuid(&client, &access_token).await?;
+        let cache_path = org_cache_path();
+        let org_uuid = match read_cached_org_uuid(&cache_path, &base_url, &access_token, unix_now())
+        {
+            Some(uuid) => uuid,
+            None => {
+                let uuid = fetch_org_uuid(&client, &base_url, &access_token).await?;
+                write_cached_org_uuid(&cache_path, &base_url, &access_token, &uuid, unix_now());
+                uuid
+            }
+        };
 
         Ok(Self {
             client,
+            base_url,
             access_token,
             org_uuid,
+            org_cache: Some(cache_path),
+            progress: opts.progress,
+            max_pages: DEFAULT_MAX_PAGES,
+            page_size: opts.page_size,
         })
     }
 
+    /// Connects to `base_url` with an explicit token, resolving the org UUID
+    /// from the profile endpoint. Skips the credential store and org cache,
+    /// and shows no progress unless [`ApiClient::with_progress`] turns it on.
+    pub async fn connect(base_url: &str, access_token: &str) -> Result<Self> {
+        let client = build_http_client(Timeouts::default())?;
+        let base_url = resolve_base_url(Some(base_url));
+        let org_uuid = fetch_org_uuid(&client, &base_url, access_token).await?;
+        Ok(Self {
+            client,
+            base_url,
+            access_token: access_token.to_string(),
+            org_uuid,
+            org_cache: None,
+            progress: false,
+            max_pages: DEFAULT_MAX_PAGES,
+            page_size: None,
+        })
+    }
+
+    /// Enables or disables the pagination progress line on stderr.
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Sets how many event pages `get_events` fetches before giving up.
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Sets how many events each page request asks for.
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
     fn headers(&self) -> Result<HeaderMap> {
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -78,24 +545,15 @@ impl ApiClient {
         Ok(headers)
     }
 
+    /// Returns all remote sessions in API order.
     pub async fn list_sessions(&self) -> Result<Vec<Session>> {
-        let url = format!("{BASE_API_URL}/v1/sessions");
-        let resp = self
-            .client
-            .get(&url)
-            .headers(self.headers()?)
-            .send()
+        let url = format!("{}/v1/sessions", self.base_url);
+        let resp = send_logged(self.client.get(&url).headers(self.headers()?))
             .await
             .context("Failed to connect to Anthropic API")?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            bail!(
-                "Failed to list sessions: {status} - {}",
-                truncate_error_body(&body)
-            );
-        }
+        let resp =
+            check_response(resp, "Failed to list sessions", self.org_cache.as_deref()).await?;
 
         let data: SessionsListResponse = resp
             .json()
@@ -104,110 +562,249 @@ impl ApiClient {
         Ok(data.data)
     }
 
+    /// Returns metadata for a single session.
     pub async fn get_session(&self, session_id: &str) -> Result<Session> {
-        let url = format!("{BASE_API_URL}/v1/sessions/{session_id}");
-        let resp = self
-            .client
-            .get(&url)
-            .headers(self.headers()?)
-            .send()
+        let url = format!("{}/v1/sessions/{session_id}", self.base_url);
+        let resp = send_logged(self.client.get(&url).headers(self.headers()?))
             .await
             .with_context(|| format!("Failed to fetch session {session_id}"))?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            bail!(
-                "Session {session_id} not found: {status} - {}",
-                truncate_error_body(&body)
-            );
-        }
+        let resp = check_response(
+            resp,
+            format!("Failed to fetch session {session_id}"),
+            self.org_cache.as_deref(),
+        )
+        .await?;
 
         resp.json()
             .await
             .with_context(|| format!("Failed to parse session {session_id} response"))
     }
 
+    /// Fetches the organization and account the access token belongs to.
+    pub async fn get_profile(&self) -> Result<ProfileResponse> {
+        fetch_profile(&self.client, &self.base_url, &self.access_token).await
+    }
+
+    /// Like [`get_session`](Self::get_session), but serves finished sessions
+    /// from an on-disk cache. `refresh` skips the cache lookup; the fetched
+    /// session still replaces the cached copy.
+    pub async fn get_session_cached(&self, session_id: &str, refresh: bool) -> Result<Session> {
+        let dir = session_cache_dir();
+        if !refresh && let Some(session) = read_cached_session(&dir, session_id) {
+            return Ok(session);
+        }
+        let session = self.get_session(session_id).await?;
+        write_cached_session(&dir, &session);
+        Ok(session)
+    }
+
+    /// Fetches events page by page; `max_events` of 0 means all.
     pub async fn get_events(
         &self,
         session_id: &str,
         max_events: usize,
     ) -> Result<Vec<SessionEvent>> {
-        let mut all_events = Vec::new();
-        let mut after_id: Option<String> = None;
+        let query = EventQuery {
+            max_events,
+            ..Default::default()
+        };
+        self.get_events_query(session_id, &query).await
+    }
 
-        loop {
-            let mut url =
-                reqwest::Url::parse(&format!("{BASE_API_URL}/v1/sessions/{session_id}/events"))
-                    .context("Failed to build events URL")?;
+    /// Fetches the events selected by `query`, page by page.
+    pub async fn get_events_query(
+        &self,
+        session_id: &str,
+        query: &EventQuery,
+    ) -> Result<Vec<SessionEvent>> {
+        let mut events = Vec::new();
+        self.for_each_event(session_id, query, |event| {
+            events.push(event);
+            Ok(ControlFlow::Continue(()))
+        })
+        .await?;
+        Ok(events)
+    }
 
-            if let Some(ref aid) = after_id {
-                url.query_pairs_mut().append_pair("after_id", aid);
-            }
+    /// Streams the events selected by `query` to `callback` as each page
+    /// arrives, so at most one page is held in memory. Returns how many
+    /// events were passed to `callback`.
+    ///
+    /// Stops with a warning after `max_pages` pages. `callback` returns
+    /// `ControlFlow::Break` to stop fetching after the event it was given; an
+    /// error from it stops pagination and is returned.
+    pub async fn for_each_event<F>(
+        &self,
+        session_id: &str,
+        query: &EventQuery,
+        mut callback: F,
+    ) -> Result<usize>
+    where
+        F: FnMut(SessionEvent) -> Result<ControlFlow<()>>,
+    {
+        let max_events = query.max_events;
+        let mut delivered = 0;
+        let mut after_id = query.after_id.clone();
+        let started = Instant::now();
+        let mut pages = 0;
+        let mut progress = FetchProgress::start(self.progress);
 
-            let resp = self
-                .client
-                .get(url)
-                .headers(self.headers()?)
-                .send()
+        loop {
+            let page = match self
+                .fetch_events_page(session_id, after_id.as_deref(), pages + 1)
+                .instrument(debug_span!("events_page", session_id, page = pages + 1))
                 .await
-                .with_context(|| {
-                    format!(
-                        "Failed to fetch events for session {session_id} (page {})",
-                        all_events.len() / 1000 + 1
-                    )
-                })?;
-
-            if !resp.status().is_success() {
-                let status = resp.status();
-                let body = resp.text().await.unwrap_or_default();
-                bail!(
-                    "Failed to fetch events for session {session_id}: {status} - {}",
-                    truncate_error_body(&body)
-                );
+            {
+                Ok(page) => page,
+                Err(err) if query.best_effort && pages > 0 => {
+                    progress.finish();
+                    eprintln!(
+                        "Warning: page {} of events for session {session_id} failed; \
+                         returning the {delivered} events fetched so far: {err:#}",
+                        pages + 1,
+                    );
+                    return Ok(delivered);
+                }
+                Err(err) => return Err(err),
+            };
+            pages += 1;
+            debug!(
+                page = pages,
+                events = page.data.len(),
+                has_more = page.has_more,
+                "fetched events page"
+            );
+            if query.log_cursors {
+                eprintln!("{}", format_page_cursors(pages, &page));
+            }
+            let mut has_more = page.has_more == Some(true);
+            for event in page.data {
+                if let Some(ref before) = query.before_id
+                    && event.uuid() == Some(before.as_str())
+                {
+                    has_more = false;
+                    break;
+                }
+                if max_events > 0 && delivered >= max_events {
+                    break;
+                }
+                match callback(event) {
+                    Ok(ControlFlow::Continue(())) => delivered += 1,
+                    Ok(ControlFlow::Break(())) => {
+                        progress.finish();
+                        return Ok(delivered + 1);
+                    }
+                    Err(err) => {
+                        progress.finish();
+                        return Err(err);
+                    }
+                }
             }
 
-            let page: EventsResponse = resp.json().await.with_context(|| {
-                format!("Failed to parse events response for session {session_id}")
-            })?;
-            all_events.extend(page.data);
-
-            eprint!("\r  Fetched {} events...", all_events.len());
+            progress.update(format_fetch_progress(
+                delivered,
+                started.elapsed(),
+                has_more,
+                max_events,
+            ));
 
-            if max_events > 0 && all_events.len() >= max_events {
-                all_events.truncate(max_events);
+            if max_events > 0 && delivered >= max_events {
                 break;
             }
 
-            if page.has_more != Some(true) {
+            if !has_more {
                 break;
             }
 
+            if pages >= self.max_pages {
+                progress.finish();
+                eprintln!(
+                    "Warning: stopped after {pages} pages of events for session {session_id}; \
+                     the API still reports more"
+                );
+                return Ok(delivered);
+            }
+
             after_id = page.last_id;
         }
-        eprintln!();
+        progress.finish();
+
+        Ok(delivered)
+    }
+
+    /// Pages through the session's events until one has `event_id` as its
+    /// UUID, fetching no further pages after that. `Ok(None)` if no event
+    /// matches.
+    pub async fn find_event(
+        &self,
+        session_id: &str,
+        event_id: &str,
+    ) -> Result<Option<SessionEvent>> {
+        let mut found = None;
+        self.for_each_event(session_id, &EventQuery::default(), |event| {
+            if event.uuid() == Some(event_id) {
+                found = Some(event);
+                return Ok(ControlFlow::Break(()));
+            }
+            Ok(ControlFlow::Continue(()))
+        })
+        .await?;
+        Ok(found)
+    }
+
+    /// Fetches one page of events; `page` is 1-based and only used in errors.
+    async fn fetch_events_page(
+        &self,
+        session_id: &str,
+        after_id: Option<&str>,
+        page: usize,
+    ) -> Result<EventsResponse> {
+        let mut url = reqwest::Url::parse(&format!(
+            "{}/v1/sessions/{session_id}/events",
+            self.base_url
+        ))
+        .context("Failed to build events URL")?;
+
+        if let Some(aid) = after_id {
+            url.query_pairs_mut().append_pair("after_id", aid);
+        }
+        if let Some(limit) = self.page_size {
+            url.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
 
-        Ok(all_events)
+        let resp = send_logged(self.client.get(url).headers(self.headers()?))
+            .await
+            .with_context(|| {
+                format!("Failed to fetch events for session {session_id} (page {page})")
+            })?;
+
+        let resp = check_response(
+            resp,
+            format!("Failed to fetch events for session {session_id} (page {page})"),
+            self.org_cache.as_deref(),
+        )
+        .await?;
+
+        resp.json().await.with_context(|| {
+            format!("Failed to parse events response for session {session_id} (page {page})")
+        })
     }
 
+    /// Returns the compact transcript from the session_ingress endpoint.
     pub async fn get_loglines(&self, session_id: &str) -> Result<Vec<Logline>> {
-        let url = format!("{BASE_API_URL}/v1/session_ingress/session/{session_id}");
-        let resp = self
-            .client
-            .get(&url)
-            .headers(self.headers()?)
-            .send()
+        let url = format!("{}/v1/session_ingress/session/{session_id}", self.base_url);
+        let resp = send_logged(self.client.get(&url).headers(self.headers()?))
             .await
             .with_context(|| format!("Failed to fetch loglines for session {session_id}"))?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            bail!(
-                "Failed to fetch loglines for session {session_id}: {status} - {}",
-                truncate_error_body(&body)
-            );
-        }
+        let resp = check_response(
+            resp,
+            format!("Failed to fetch loglines for session {session_id}"),
+            self.org_cache.as_deref(),
+        )
+        .await?;
 
         let data: IngressResponse = resp
             .json()
@@ -233,22 +830,58 @@ pub fn credentials_file_path() -> PathBuf {
     resolve_credentials_path(std::env::var("CLAUDE_CONFIG_DIR").ok().as_deref())
 }
 
+/// Parses credentials in Claude Code's `{"claudeAiOauth": {...}}` shape, or
+/// failing that a flat `{accessToken, expiresAt, scopes}` object as found in
+/// hand-assembled CI files. If neither fits, the error is the nested shape's.
+fn parse_credentials_json(json: &str) -> serde_json::Result<OAuthCredentials> {
+    serde_json::from_str::<OAuthCredentials>(json).or_else(|nested_err| {
+        serde_json::from_str::<OAuthToken>(json)
+            .map(|token| OAuthCredentials {
+                claude_ai_oauth: token,
+            })
+            .map_err(|_| nested_err)
+    })
+}
+
+/// The warning for a credentials file whose Unix `mode` lets group or other
+/// users read or change it, or `None` if only the owner can.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn credentials_permission_warning(path: &Path, mode: u32) -> Option<String> {
+    (mode & 0o077 != 0).then(|| {
+        format!(
+            "Warning: {} is accessible by other users (mode {:03o}) and holds your access \
+             token. Run `chmod 600 {}` to restrict it.",
+            path.display(),
+            mode & 0o777,
+            path.display()
+        )
+    })
+}
+
 fn load_credentials_from_file(path: &std::path::Path) -> Result<OAuthCredentials> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = std::fs::metadata(path)
+            && let Some(warning) = credentials_permission_warning(path, meta.permissions().mode())
+        {
+            eprintln!("{warning}");
+        }
+    }
     let json_str = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read credentials from {}", path.display()))?;
-    serde_json::from_str(json_str.trim())
+    parse_credentials_json(json_str.trim())
         .with_context(|| format!("Failed to parse credentials JSON from {}", path.display()))
 }
 
+/// Keychain service name Claude Code stores its OAuth credentials under.
+const KEYCHAIN_SERVICE: &str = "Claude Code-credentials";
+
+/// Loads credentials from the macOS Keychain; `Ok(None)` if there is no entry.
 #[cfg(target_os = "macos")]
-fn load_credentials_from_keychain() -> Result<OAuthCredentials> {
+fn load_credentials_from_keychain() -> Result<Option<OAuthCredentials>> {
     let output = std::process::Command::new("security")
-        .args([
-            "find-generic-password",
-            "-s",
-            "Claude Code-credentials",
-            "-w",
-        ])
+        .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-w"])
         .output()
         .context(
             "Failed to run 'security' command. \
@@ -256,27 +889,65 @@ fn load_credentials_from_keychain() -> Result<OAuthCredentials> {
         )?;
 
     if !output.status.success() {
-        bail!("No Claude Code credentials found in macOS Keychain.");
+        return Ok(None);
     }
+    parse_keychain_secret(&output.stdout).map(Some)
+}
+
+/// Parses the secret printed by `security find-generic-password -w`. Errors
+/// name the Keychain item, since the raw conversion errors don't say where
+/// the bad data came from.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_keychain_secret(secret: &[u8]) -> Result<OAuthCredentials> {
+    let json_str = std::str::from_utf8(secret).map_err(|e| {
+        anyhow::anyhow!(
+            "Keychain item '{KEYCHAIN_SERVICE}' is not valid UTF-8 (bad byte at offset {}); \
+             run 'claude login' to store it again",
+            e.valid_up_to()
+        )
+    })?;
+    parse_credentials_json(json_str.trim()).with_context(|| {
+        format!("Failed to parse credentials JSON from Keychain item '{KEYCHAIN_SERVICE}'")
+    })
+}
 
-    let json_str =
-        String::from_utf8(output.stdout).context("Credentials output is not valid UTF-8")?;
-    serde_json::from_str(json_str.trim()).context("Failed to parse credentials JSON from Keychain")
+/// Builds credentials from a `CLAUDE_ACCESS_TOKEN` value; blank values are ignored.
+fn credentials_from_env_token(token: Option<String>) -> Option<OAuthCredentials> {
+    let token = token?.trim().to_string();
+    if token.is_empty() {
+        return None;
+    }
+    Some(OAuthCredentials {
+        claude_ai_oauth: OAuthToken {
+            access_token: token,
+            expires_at: ENV_TOKEN_EXPIRES_AT,
+            scopes: Vec::new(),
+        },
+    })
 }
 
-fn load_credentials() -> Result<OAuthCredentials> {
+/// Loads OAuth credentials: `CLAUDE_ACCESS_TOKEN` if set, otherwise the
+/// platform's credential store.
+pub fn load_credentials() -> Result<(OAuthCredentials, CredentialSource)> {
+    if let Some(creds) = credentials_from_env_token(std::env::var(ACCESS_TOKEN_ENV).ok()) {
+        return Ok((creds, CredentialSource::Env));
+    }
+
     // On macOS, try Keychain first, then fall back to file.
     #[cfg(target_os = "macos")]
     {
-        if let Ok(creds) = load_credentials_from_keychain() {
-            return Ok(creds);
+        match load_credentials_from_keychain() {
+            Ok(Some(creds)) => return Ok((creds, CredentialSource::Keychain)),
+            Ok(None) => {}
+            Err(err) => eprintln!("Warning: {err:#}"),
         }
     }
 
     // All platforms: try the credentials file.
     let path = credentials_file_path();
     if path.exists() {
-        return load_credentials_from_file(&path);
+        let creds = load_credentials_from_file(&path)?;
+        return Ok((creds, CredentialSource::File(path)));
     }
 
     #[cfg(target_os = "macos")]
@@ -295,30 +966,195 @@ fn load_credentials() -> Result<OAuthCredentials> {
     );
 }
 
-async fn fetch_org_uuid(client: &reqwest::Client, token: &str) -> Result<String> {
-    let url = format!("{BASE_API_URL}/api/oauth/profile");
-    let resp = client
-        .get(&url)
-        .header(AUTHORIZATION, format!("Bearer {token}"))
-        .header(CONTENT_TYPE, "application/json")
-        .send()
-        .await
-        .context("Failed to fetch profile")?;
+// ── Org UUID cache ───────────────────────────────────────────────────
 
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        bail!(
-            "Failed to fetch profile (token may be expired): {status} - {}",
-            truncate_error_body(&body)
-        );
+/// Cached result of the profile lookup, stored next to the credentials file.
+#[derive(Serialize, Deserialize)]
+struct OrgCache {
+    /// [`token_hash`] of the API base URL and access token the UUID was
+    /// resolved with; never the token itself.
+    token_hash: String,
+    org_uuid: String,
+    cached_at: u64,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 64-bit FNV-1a of `base_url` and `token`, as hex. Unlike `DefaultHasher`,
+/// the result doesn't change between Rust releases, so cache entries written
+/// by one build still match in the next.
+fn token_hash(base_url: &str, token: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let bytes = base_url.bytes().chain([0]).chain(token.bytes());
+    let hash = bytes.fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+fn org_cache_path() -> PathBuf {
+    credentials_file_path().with_file_name(ORG_CACHE_FILE)
+}
+
+/// Returns the cached org UUID if it was stored for this API and token
+/// within the TTL.
+fn read_cached_org_uuid(path: &Path, base_url: &str, token: &str, now: u64) -> Option<String> {
+    let json_str = std::fs::read_to_string(path).ok()?;
+    let cache: OrgCache = serde_json::from_str(&json_str).ok()?;
+    let fresh = now.saturating_sub(cache.cached_at) < ORG_CACHE_TTL_SECS;
+    (fresh && cache.token_hash == token_hash(base_url, token)).then_some(cache.org_uuid)
+}
+
+/// Best-effort: a cache that can't be written just means another profile fetch.
+fn write_cached_org_uuid(path: &Path, base_url: &str, token: &str, org_uuid: &str, now: u64) {
+    let cache = OrgCache {
+        token_hash: token_hash(base_url, token),
+        org_uuid: org_uuid.to_string(),
+        cached_at: now,
+    };
+    if let Ok(json) = serde_json::to_string(&cache) {
+        let _ = std::fs::write(path, json);
     }
+}
+
+fn invalidate_org_cache(path: &Path) {
+    let _ = std::fs::remove_file(path);
+}
+
+fn build_http_client(timeouts: Timeouts) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(timeout) = timeouts.request {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = timeouts.connect {
+        builder = builder.connect_timeout(timeout);
+    }
+    builder.build().context("Failed to build HTTP client")
+}
+
+/// Confirms `token` against the profile endpoint, bypassing the org UUID
+/// cache, and returns the organization UUID it resolves to.
+pub async fn verify_token(token: &str, timeouts: Timeouts) -> Result<String> {
+    fetch_org_uuid(&build_http_client(timeouts)?, &base_url(), token).await
+}
+
+// ── Session cache ────────────────────────────────────────────────────
+
+fn session_cache_dir() -> PathBuf {
+    credentials_file_path().with_file_name(SESSION_CACHE_DIR)
+}
+
+/// Running and idle sessions can still change, so only finished ones are cached.
+fn is_cacheable(session: &Session) -> bool {
+    session.session_status.is_some() && !session.is_live()
+}
+
+fn read_cached_session(dir: &Path, session_id: &str) -> Option<Session> {
+    let json_str = std::fs::read_to_string(dir.join(format!("{session_id}.json"))).ok()?;
+    let session: Session = serde_json::from_str(&json_str).ok()?;
+    is_cacheable(&session).then_some(session)
+}
+
+/// Best-effort, like the org UUID cache.
+fn write_cached_session(dir: &Path, session: &Session) {
+    if !is_cacheable(session) || validate_session_id(&session.id).is_err() {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(session)
+        && std::fs::create_dir_all(dir).is_ok()
+    {
+        let _ = std::fs::write(dir.join(format!("{}.json", session.id)), json);
+    }
+}
+
+// ── Summary cache ────────────────────────────────────────────────────
+
+/// A computed `summary`, valid while the session's `updated_at` is unchanged.
+#[derive(Serialize, Deserialize)]
+struct CachedSummary<T> {
+    updated_at: String,
+    summary: T,
+}
+
+fn summary_cache_file(dir: &Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{session_id}.summary.json"))
+}
+
+/// Returns the summary cached for `session_id` if it was computed while the
+/// session's `updated_at` was `updated_at`.
+pub fn read_cached_summary<T: DeserializeOwned>(session_id: &str, updated_at: &str) -> Option<T> {
+    read_cached_summary_in(&session_cache_dir(), session_id, updated_at)
+}
+
+/// Stores `summary` for `session_id` at `updated_at`, replacing any older
+/// entry. Best-effort, like the session cache.
+pub fn write_cached_summary<T: Serialize>(session_id: &str, updated_at: &str, summary: &T) {
+    write_cached_summary_in(&session_cache_dir(), session_id, updated_at, summary);
+}
+
+fn read_cached_summary_in<T: DeserializeOwned>(
+    dir: &Path,
+    session_id: &str,
+    updated_at: &str,
+) -> Option<T> {
+    let json_str = std::fs::read_to_string(summary_cache_file(dir, session_id)).ok()?;
+    let cached: CachedSummary<T> = serde_json::from_str(&json_str).ok()?;
+    (cached.updated_at == updated_at).then_some(cached.summary)
+}
 
-    let profile: ProfileResponse = resp
-        .json()
+fn write_cached_summary_in<T: Serialize>(
+    dir: &Path,
+    session_id: &str,
+    updated_at: &str,
+    summary: &T,
+) {
+    if validate_session_id(session_id).is_err() {
+        return;
+    }
+    let cached = CachedSummary {
+        updated_at: updated_at.to_string(),
+        summary,
+    };
+    if let Ok(json) = serde_json::to_string(&cached)
+        && std::fs::create_dir_all(dir).is_ok()
+    {
+        let _ = std::fs::write(summary_cache_file(dir, session_id), json);
+    }
+}
+
+async fn fetch_org_uuid(client: &reqwest::Client, base_url: &str, token: &str) -> Result<String> {
+    Ok(fetch_profile(client, base_url, token)
+        .await?
+        .organization
+        .uuid)
+}
+
+async fn fetch_profile(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<ProfileResponse> {
+    let url = format!("{base_url}/api/oauth/profile");
+    let resp = send_logged(
+        client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .header(CONTENT_TYPE, "application/json"),
+    )
+    .await
+    .context("Failed to fetch profile")?;
+
+    let resp = check_response(resp, "Failed to fetch profile", None).await?;
+
+    resp.json()
         .await
-        .context("Failed to parse profile response")?;
-    Ok(profile.organization.uuid)
+        .context("Failed to parse profile response")
 }
 
 #[cfg(test)]
@@ -355,6 +1191,279 @@ mod tests {
         assert!(err.to_string().contains("Invalid session ID format"));
     }
 
+    // ── Fetch progress ─────────────────────────────────────────────
+
+    #[test]
+    fn fetch_progress_shows_rate() {
+        let msg = format_fetch_progress(2000, Duration::from_secs(4), false, 0);
+        assert_eq!(msg, "Fetched 2000 events (500/s)...");
+    }
+
+    #[test]
+    fn fetch_progress_zero_elapsed_no_panic() {
+        let msg = format_fetch_progress(1000, Duration::ZERO, true, 0);
+        assert_eq!(msg, "Fetched 1000 events (0/s, more pending)...");
+    }
+
+    #[test]
+    fn fetch_progress_estimates_remaining_with_cap() {
+        let msg = format_fetch_progress(1000, Duration::from_secs(1), true, 2500);
+        assert_eq!(msg, "Fetched 1000 events (1000/s, ~1500 remaining)...");
+    }
+
+    // ── Page cursors ───────────────────────────────────────────────
+
+    #[test]
+    fn page_cursors_show_ids_and_has_more() {
+        let page: EventsResponse = serde_json::from_value(serde_json::json!({
+            "data": [{ "type": "user", "message": { "content": "hi" } }],
+            "first_id": "e1",
+            "last_id": "e1",
+            "has_more": true
+        }))
+        .unwrap();
+        assert_eq!(
+            format_page_cursors(2, &page),
+            "page 2: 1 events, first_id=e1 last_id=e1 has_more=true"
+        );
+
+        let empty: EventsResponse =
+            serde_json::from_value(serde_json::json!({ "data": [] })).unwrap();
+        assert_eq!(
+            format_page_cursors(1, &empty),
+            "page 1: 0 events, first_id=- last_id=- has_more=-"
+        );
+    }
+
+    // ── ApiError ────────────────────────────────────────────────────
+
+    #[test]
+    fn api_error_classifies_status() {
+        let err = |code: u16| ApiError::from_status("ctx", StatusCode::from_u16(code).unwrap(), "");
+        assert!(matches!(err(401), ApiError::Unauthorized { .. }));
+        assert!(matches!(err(404), ApiError::NotFound { .. }));
+        assert!(matches!(err(429), ApiError::RateLimited { .. }));
+        assert!(matches!(err(502), ApiError::Server { .. }));
+        assert!(matches!(err(400), ApiError::Other { .. }));
+    }
+
+    #[test]
+    fn api_error_message_truncates_body() {
+        let body = "x".repeat(MAX_ERROR_BODY_LEN + 100);
+        let err = ApiError::from_status("Failed to list sessions", StatusCode::FORBIDDEN, &body);
+        let msg = err.to_string();
+        assert!(msg.starts_with("Failed to list sessions: 403 Forbidden - xxx"));
+        assert!(msg.len() < MAX_ERROR_BODY_LEN + 50);
+    }
+
+    #[test]
+    fn api_error_unauthorized_message_omits_body() {
+        let err = ApiError::from_status(
+            "Failed to list sessions",
+            StatusCode::UNAUTHORIZED,
+            r#"{"type":"error","error":{"type":"authentication_error"}}"#,
+        );
+        assert_eq!(
+            err.to_string(),
+            "Failed to list sessions: the OAuth token is invalid or expired (401 Unauthorized)"
+        );
+    }
+
+    #[test]
+    fn api_error_downcasts_through_context() {
+        let err = anyhow::Error::new(ApiError::from_status("ctx", StatusCode::UNAUTHORIZED, ""))
+            .context("outer");
+        assert!(matches!(
+            err.downcast_ref::<ApiError>(),
+            Some(ApiError::Unauthorized { .. })
+        ));
+    }
+
+    // ── Org UUID cache ─────────────────────────────────────────────
+
+    const API: &str = "https://api.example.com";
+
+    #[test]
+    fn org_cache_roundtrip() {
+        let dir = std::env::temp_dir().join("cta-test-org-cache");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(ORG_CACHE_FILE);
+
+        write_cached_org_uuid(&path, API, "tok", "org-123", 1000);
+        assert_eq!(
+            read_cached_org_uuid(&path, API, "tok", 1000 + 60).as_deref(),
+            Some("org-123")
+        );
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("tok\""));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn org_cache_rejects_expired_other_token_or_other_api() {
+        let dir = std::env::temp_dir().join("cta-test-org-cache-stale");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(ORG_CACHE_FILE);
+
+        write_cached_org_uuid(&path, API, "tok", "org-123", 1000);
+        assert!(read_cached_org_uuid(&path, API, "tok", 1000 + ORG_CACHE_TTL_SECS).is_none());
+        assert!(read_cached_org_uuid(&path, API, "other", 1000).is_none());
+        assert!(read_cached_org_uuid(&path, "http://localhost:8080", "tok", 1000).is_none());
+
+        invalidate_org_cache(&path);
+        assert!(read_cached_org_uuid(&path, API, "tok", 1000).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn token_hash_is_stable() {
+        assert_eq!(token_hash(API, "tok"), "5ceec238405a88b7");
+        assert_ne!(
+            token_hash(API, "tok"),
+            token_hash("https://api.example.co", "mtok")
+        );
+    }
+
+    // ── Session cache ──────────────────────────────────────────────
+
+    fn session_with_status(status: Option<&str>) -> Session {
+        serde_json::from_value(serde_json::json!({
+            "id": "session_01cachetest0000",
+            "title": "Cached",
+            "session_status": status,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn session_cache_roundtrip_for_finished_session() {
+        let dir = std::env::temp_dir().join("cta-test-session-cache");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        write_cached_session(&dir, &session_with_status(Some("completed")));
+        let cached = read_cached_session(&dir, "session_01cachetest0000").unwrap();
+        assert_eq!(cached.title.as_deref(), Some("Cached"));
+        assert!(read_cached_session(&dir, "session_01othersession00").is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn session_cache_skips_live_sessions() {
+        let dir = std::env::temp_dir().join("cta-test-session-cache-live");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        for status in [Some("running"), Some("idle"), None] {
+            write_cached_session(&dir, &session_with_status(status));
+            assert!(read_cached_session(&dir, "session_01cachetest0000").is_none());
+        }
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn session_cache_ignores_stale_live_entry() {
+        let dir = std::env::temp_dir().join("cta-test-session-cache-stale");
+        std::fs::create_dir_all(&dir).unwrap();
+        let running = serde_json::to_string(&session_with_status(Some("running"))).unwrap();
+        std::fs::write(dir.join("session_01cachetest0000.json"), running).unwrap();
+
+        assert!(read_cached_session(&dir, "session_01cachetest0000").is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn summary_cache_is_keyed_by_updated_at() {
+        let dir = std::env::temp_dir().join("cta-test-summary-cache");
+        let _ = std::fs::remove_dir_all(&dir);
+        let id = "session_01cachetest0000";
+
+        write_cached_summary_in(&dir, id, "2025-01-01T00:00:00Z", &vec![1, 2, 3]);
+        let hit: Option<Vec<u32>> = read_cached_summary_in(&dir, id, "2025-01-01T00:00:00Z");
+        assert_eq!(hit, Some(vec![1, 2, 3]));
+        let stale: Option<Vec<u32>> = read_cached_summary_in(&dir, id, "2025-01-02T00:00:00Z");
+        assert_eq!(stale, None);
+
+        write_cached_summary_in(&dir, "../escape", "t", &1);
+        assert!(!dir.join("../escape.summary.json").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ── Environment token override ─────────────────────────────────
+
+    #[test]
+    fn env_token_builds_non_expiring_credentials() {
+        let creds = credentials_from_env_token(Some(" tok_env \n".to_string())).unwrap();
+        assert_eq!(creds.claude_ai_oauth.access_token, "tok_env");
+        let expires = creds.claude_ai_oauth.expires_at_utc().unwrap();
+        assert_eq!(expires.format("%Y").to_string(), "9999");
+    }
+
+    #[test]
+    fn env_token_blank_or_unset_is_ignored() {
+        assert!(credentials_from_env_token(None).is_none());
+        assert!(credentials_from_env_token(Some("   ".to_string())).is_none());
+    }
+
+    // ── Base URL override ──────────────────────────────────────────
+
+    #[test]
+    fn base_url_defaults_to_production() {
+        assert_eq!(resolve_base_url(None), DEFAULT_BASE_API_URL);
+        assert_eq!(resolve_base_url(Some("  ")), DEFAULT_BASE_API_URL);
+    }
+
+    #[test]
+    fn base_url_override_drops_trailing_slash() {
+        assert_eq!(
+            resolve_base_url(Some("http://127.0.0.1:8080/")),
+            "http://127.0.0.1:8080"
+        );
+    }
+
+    // ── Timeouts ───────────────────────────────────────────────────
+
+    #[test]
+    fn timeout_flag_beats_env_and_zero_disables() {
+        let default = Duration::from_secs(30);
+        let env = Some("90".to_string());
+        assert_eq!(
+            resolve_timeout(Some(5), "T", env.clone(), default).unwrap(),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            resolve_timeout(None, "T", env, default).unwrap(),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            resolve_timeout(None, "T", None, default).unwrap(),
+            Some(default)
+        );
+        assert_eq!(resolve_timeout(Some(0), "T", None, default).unwrap(), None);
+        assert_eq!(
+            resolve_timeout(None, "T", Some("0".to_string()), default).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn timeout_env_must_be_whole_seconds() {
+        let err = resolve_timeout(
+            None,
+            "CTA_TIMEOUT",
+            Some("1.5".to_string()),
+            REQUEST_TIMEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid CTA_TIMEOUT value '1.5': expected whole seconds"
+        );
+    }
+
     // ── Credential path resolution ─────────────────────────────────
 
     #[test]
@@ -372,6 +1481,30 @@ mod tests {
         );
     }
 
+    // ── Keychain secret parsing ───────────────────────────────────
+
+    #[test]
+    fn keychain_secret_parses_trimmed_json() {
+        let secret = concat!(
+            r#"{"claudeAiOauth":{"accessToken":"tok","expiresAt":1,"scopes":[]}}"#,
+            "\n"
+        );
+        let creds = parse_keychain_secret(secret.as_bytes()).unwrap();
+        assert_eq!(creds.claude_ai_oauth.access_token, "tok");
+    }
+
+    #[test]
+    fn keychain_secret_invalid_utf8_names_the_item() {
+        let err = parse_keychain_secret(b"{\"claudeAiOauth\xff").unwrap_err();
+        let msg = err.to_string();
+        assert!(
+            msg.contains("Keychain item 'Claude Code-credentials'"),
+            "{msg}"
+        );
+        assert!(msg.contains("offset 15"), "{msg}");
+        assert!(msg.contains("claude login"), "{msg}");
+    }
+
     #[test]
     fn load_credentials_from_file_valid() {
         let dir = std::env::temp_dir().join("cta-test-creds");
@@ -396,6 +1529,39 @@ mod tests {
         std::fs::remove_dir_all(&dir).unwrap();
     }
 
+    #[test]
+    fn parse_credentials_accepts_flat_token_object() {
+        let creds = parse_credentials_json(
+            r#"{ "accessToken": "flat_token", "expiresAt": 1700000000000, "scopes": [] }"#,
+        )
+        .unwrap();
+        assert_eq!(creds.claude_ai_oauth.access_token, "flat_token");
+        assert_eq!(creds.claude_ai_oauth.expires_at, 1_700_000_000_000);
+    }
+
+    #[test]
+    fn parse_credentials_reports_the_nested_shape_error() {
+        let err =
+            parse_credentials_json(r#"{ "claudeAiOauth": { "accessToken": "t" } }"#).unwrap_err();
+        assert!(err.to_string().contains("expiresAt"), "{err}");
+        assert!(parse_credentials_json(r#"{ "token": "t" }"#).is_err());
+    }
+
+    #[test]
+    fn credentials_permission_warning_flags_group_and_world_access() {
+        let path = Path::new("/home/me/.claude/.credentials.json");
+        assert_eq!(credentials_permission_warning(path, 0o100600), None);
+        assert_eq!(credentials_permission_warning(path, 0o400), None);
+        let warning = credentials_permission_warning(path, 0o100644).unwrap();
+        assert!(warning.contains("mode 644"), "{warning}");
+        assert!(
+            warning.contains("chmod 600 /home/me/.claude/.credentials.json"),
+            "{warning}"
+        );
+        assert!(credentials_permission_warning(path, 0o640).is_some());
+        assert!(credentials_permission_warning(path, 0o602).is_some());
+    }
+
     #[test]
     fn load_credentials_from_file_missing() {
         let path = PathBuf::from("/tmp/nonexistent-cta-creds/.credentials.json");
@@ -1,8 +1,123 @@
 use chrono::{DateTime, Utc};
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 
+use crate::export::ToolUsageRow;
+use crate::stats::{Bucket, ToolStats};
+use crate::theme::{Role, Themed};
 use crate::types::*;
 
+/// Rendering options for `read` output.
+#[derive(Debug, Default, Clone)]
+pub struct DisplayOptions {
+    /// Column at which text content is hard-wrapped (0 = no wrapping).
+    pub wrap: usize,
+    /// Print each event on a single line instead of the full rendering.
+    pub compact: bool,
+    /// Print tool_result content in full instead of a 200-char preview.
+    pub full_results: bool,
+    /// Render without ANSI colors, e.g. for text exports.
+    pub plain: bool,
+    /// Prefix each event with the first 8 characters of its UUID.
+    pub show_uuid: bool,
+    /// Show event times relative to this instant ("2h ago") instead of as UTC.
+    pub relative_to: Option<DateTime<Utc>>,
+    /// Print tool_use input as indented, pretty-printed JSON.
+    pub pretty_input: bool,
+    /// Preview length replacing the built-in per-field limits
+    /// (`Some(0)` = never truncate).
+    pub truncate: Option<usize>,
+    /// Show only the text blocks of assistant messages.
+    pub text_only: bool,
+    /// Strip trailing whitespace and collapse long runs of blank lines; see
+    /// [`trim_blank_runs`].
+    pub trim: bool,
+    /// Print each event as one line of JSON instead of rendering it.
+    pub raw: bool,
+}
+
+impl DisplayOptions {
+    /// Cuts `s` to the `--truncate` length when set, otherwise to `default` chars.
+    fn preview(&self, s: &str, default: usize) -> String {
+        match self.truncate.unwrap_or(default) {
+            0 => s.to_string(),
+            max => truncate_str(s, max),
+        }
+    }
+}
+
+/// Returns the current terminal width, or 0 if stdout is not a terminal.
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(w, _)| w.0 as usize)
+        .unwrap_or(0)
+}
+
+/// Hard-wrap `line` so that `indent` + each output line fits in `width` columns.
+///
+/// Breaks at the last space before the limit when possible, otherwise mid-word.
+/// Continuation lines keep the original line's leading whitespace.
+fn wrap_line(line: &str, indent: usize, width: usize) -> Vec<String> {
+    let leading: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+    let lead_len = leading.chars().count();
+    let avail = width.saturating_sub(indent);
+    if width == 0 || avail <= lead_len + 1 || line.chars().count() <= avail {
+        return vec![line.to_string()];
+    }
+
+    let room = avail - lead_len;
+    let mut rest: Vec<char> = line.chars().skip(lead_len).collect();
+    let mut out = Vec::new();
+    while rest.len() > room {
+        let split = rest[..=room]
+            .iter()
+            .rposition(|c| *c == ' ')
+            .filter(|&i| i > 0)
+            .unwrap_or(room);
+        let head: String = rest[..split].iter().collect();
+        out.push(format!("{leading}{}", head.trim_end()));
+        let skip = rest[split..].iter().take_while(|c| **c == ' ').count();
+        rest.drain(..split + skip);
+    }
+    if !rest.is_empty() {
+        out.push(format!("{leading}{}", rest.iter().collect::<String>()));
+    }
+    out
+}
+
+/// Strips trailing whitespace from every line and collapses runs of three or
+/// more blank lines into one.
+fn trim_blank_runs(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let mut out: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        if !lines[i].is_empty() {
+            out.push(lines[i]);
+            i += 1;
+            continue;
+        }
+        let run = lines[i..].iter().take_while(|l| l.is_empty()).count();
+        out.extend(std::iter::repeat_n("", if run >= 3 { 1 } else { run }));
+        i += run;
+    }
+    out.join("\n")
+}
+
+/// Split `text` into lines with a two-space indent, wrapping per `opts`.
+fn indented_lines(text: &str, opts: &DisplayOptions) -> Vec<String> {
+    let trimmed;
+    let text = if opts.trim {
+        trimmed = trim_blank_runs(text);
+        trimmed.as_str()
+    } else {
+        text
+    };
+    text.lines()
+        .flat_map(|line| wrap_line(line, 2, opts.wrap))
+        .map(|wrapped| format!("  {wrapped}"))
+        .collect()
+}
+
 /// Truncate a string to at most `max_chars` characters, appending "..." if truncated.
 fn truncate_str(s: &str, max_chars: usize) -> String {
     if s.chars().count() <= max_chars {
@@ -13,6 +128,44 @@ fn truncate_str(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Formats a byte count with binary units, e.g. "512 B" or "3.4 MiB".
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Formats a duration in milliseconds, e.g. "850ms", "42s", or "1h 2m 3s".
+/// Zero components are left out.
+pub fn format_duration(ms: u64) -> String {
+    if ms == 0 {
+        return "0s".to_string();
+    }
+    if ms < 1000 {
+        return format!("{ms}ms");
+    }
+    let secs = ms / 1000;
+    let parts = [
+        (secs / 3600, "h"),
+        (secs % 3600 / 60, "m"),
+        (secs % 60, "s"),
+    ];
+    parts
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn format_timestamp(ts: &str) -> String {
     if let Ok(dt) = ts.parse::<DateTime<Utc>>() {
         dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
@@ -21,13 +174,34 @@ pub fn format_timestamp(ts: &str) -> String {
     }
 }
 
+/// Formats `ts` relative to `now` in its largest whole unit, e.g. "45s ago",
+/// "2h ago", or "in 3m" for future times. Unparseable input is returned as is.
+pub fn format_relative(ts: &str, now: DateTime<Utc>) -> String {
+    let Ok(dt) = ts.parse::<DateTime<Utc>>() else {
+        return ts.to_string();
+    };
+    let secs = (now - dt).num_seconds();
+    let abs = secs.unsigned_abs();
+    let amount = match abs {
+        0..60 => format!("{abs}s"),
+        60..3600 => format!("{}m", abs / 60),
+        3600..86400 => format!("{}h", abs / 3600),
+        _ => format!("{}d", abs / 86400),
+    };
+    if secs < 0 {
+        format!("in {amount}")
+    } else {
+        format!("{amount} ago")
+    }
+}
+
 pub fn status_colored(status: &str) -> String {
     match status {
-        "running" => status.green().bold().to_string(),
-        "idle" => status.yellow().to_string(),
-        "completed" => status.blue().to_string(),
-        "error" | "failed" => status.red().bold().to_string(),
-        _ => status.dimmed().to_string(),
+        "running" => status.themed(Role::Success).bold().to_string(),
+        "idle" => status.themed(Role::Warning).to_string(),
+        "completed" => status.themed(Role::Info).to_string(),
+        "error" | "failed" => status.themed(Role::Error).bold().to_string(),
+        _ => status.themed(Role::Muted).to_string(),
     }
 }
 
@@ -50,37 +224,218 @@ pub fn print_session_row(s: &Session) {
     println!(
         "  {} {} {}",
         status_colored(status),
-        s.id.dimmed(),
-        updated.dimmed()
+        s.id.themed(Role::Muted),
+        updated.themed(Role::Muted)
     );
     println!("    {}", title.bold());
     if !repo.is_empty() {
-        println!("    {}", repo.dimmed());
+        println!("    {}", repo.themed(Role::Muted));
+    }
+    println!();
+}
+
+/// True if source `url` points at outcome `repo`, which may be a full URL
+/// or an `owner/name` path.
+fn source_matches_repo(url: &str, repo: &str) -> bool {
+    let clean = |s: &str| {
+        s.trim_end_matches('/')
+            .trim_end_matches(".git")
+            .to_lowercase()
+    };
+    let (url, repo) = (clean(url), clean(repo));
+    !repo.is_empty() && (url == repo || url.ends_with(&format!("/{repo}")))
+}
+
+/// "from <short revision> → pushed <branches>" for each git outcome whose
+/// starting revision is known: the source with the same repo, or the only
+/// source when there is just one. Outcomes without branches are skipped.
+pub fn change_lines(ctx: &SessionContext) -> Vec<String> {
+    let sources = ctx.sources.as_deref().unwrap_or_default();
+    let outcomes = ctx.outcomes.as_deref().unwrap_or_default();
+    outcomes
+        .iter()
+        .filter_map(|out| {
+            let git = out.git_info.as_ref()?;
+            let branches = git.branches.as_deref().filter(|b| !b.is_empty())?;
+            let repo = git.repo.as_deref().unwrap_or("");
+            let source = sources
+                .iter()
+                .find(|s| {
+                    s.url
+                        .as_deref()
+                        .is_some_and(|u| source_matches_repo(u, repo))
+                })
+                .or(match sources {
+                    [only] => Some(only),
+                    _ => None,
+                })?;
+            let revision = source.revision.as_deref().filter(|r| !r.is_empty())?;
+            let short: String = revision.chars().take(7).collect();
+            Some(format!(
+                "from {short} \u{2192} pushed {}",
+                branches.join(", ")
+            ))
+        })
+        .collect()
+}
+
+/// Lines for `branches`: each git outcome's repo and push type, then its
+/// branches. Outcomes without git info are skipped.
+pub fn branch_lines(session: &Session) -> Vec<String> {
+    let gits: Vec<&GitInfo> = session
+        .session_context
+        .as_ref()
+        .and_then(|ctx| ctx.outcomes.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|out| out.git_info.as_ref())
+        .collect();
+    if gits.is_empty() {
+        return vec![format!(
+            "  {}",
+            "No git outcomes recorded.".themed(Role::Muted)
+        )];
+    }
+
+    let mut lines = Vec::new();
+    for (i, git) in gits.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        lines.push(format!(
+            "  {}: {}",
+            "Repo".themed(Role::Muted),
+            git.repo.as_deref().unwrap_or("(unknown)")
+        ));
+        lines.push(format!(
+            "  {}: {}",
+            "Push type".themed(Role::Muted),
+            git.git_type.as_deref().unwrap_or("(unknown)")
+        ));
+        match git.branches.as_deref() {
+            Some(branches) if !branches.is_empty() => {
+                lines.push(format!("  {}:", "Branches".themed(Role::Muted)));
+                for b in branches {
+                    lines.push(format!("    - {}", b.themed(Role::Success)));
+                }
+            }
+            _ => lines.push(format!(
+                "  {}: {}",
+                "Branches".themed(Role::Muted),
+                "(none)".themed(Role::Muted)
+            )),
+        }
+    }
+    lines
+}
+
+pub fn print_branches(session: &Session) {
+    println!(
+        "\n{} {}\n",
+        "Branches for".bold(),
+        session.id.themed(Role::Accent)
+    );
+    for line in branch_lines(session) {
+        println!("{line}");
     }
     println!();
 }
 
-pub fn print_session_detail(session: &Session) {
+/// Print a labeled list, one item per line, or "(none)" if empty or missing.
+fn print_list(label: &str, items: Option<&Vec<String>>) {
+    match items {
+        Some(items) if !items.is_empty() => {
+            println!("  {}:", label.themed(Role::Muted));
+            for item in items {
+                println!("    - {item}");
+            }
+        }
+        _ => println!(
+            "  {}: {}",
+            label.themed(Role::Muted),
+            "(none)".themed(Role::Muted)
+        ),
+    }
+}
+
+/// "N (id, id, ...)" summary of attached knowledge bases.
+fn format_id_count(ids: &[String]) -> String {
+    format!("{} ({})", ids.len(), ids.join(", "))
+}
+
+/// Print the fields only shown by `show --full`.
+fn print_session_extras(session: &Session) {
+    println!("\n{}\n", "Configuration".bold());
+    print_list("Mount paths", session.active_mount_paths.as_ref());
+
+    match session.metadata.as_ref() {
+        Some(meta) if !meta.is_null() => {
+            println!("  {}:", "Metadata".themed(Role::Muted));
+            for line in metadata_lines(meta) {
+                println!("    {line}");
+            }
+        }
+        _ => println!(
+            "  {}: {}",
+            "Metadata".themed(Role::Muted),
+            "(none)".themed(Role::Muted)
+        ),
+    }
+}
+
+/// Short form of a metadata value: scalars as-is (strings unquoted), arrays
+/// by length, objects by key count.
+fn metadata_scalar(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) => format!("[{} items]", items.len()),
+        serde_json::Value::Object(map) => format!("{{{} keys}}", map.len()),
+        other => other.to_string(),
+    }
+}
+
+/// Lists session metadata as `key: value` lines. Nested objects are expanded
+/// one level, indented under their key; anything deeper is summarized.
+fn metadata_lines(meta: &serde_json::Value) -> Vec<String> {
+    let Some(map) = meta.as_object() else {
+        return vec![metadata_scalar(meta)];
+    };
+    let mut lines = Vec::new();
+    for (key, value) in map {
+        match value.as_object() {
+            Some(nested) if !nested.is_empty() => {
+                lines.push(format!("{key}:"));
+                for (nested_key, nested_value) in nested {
+                    lines.push(format!("  {nested_key}: {}", metadata_scalar(nested_value)));
+                }
+            }
+            _ => lines.push(format!("{key}: {}", metadata_scalar(value))),
+        }
+    }
+    lines
+}
+
+pub fn print_session_detail(session: &Session, full: bool) {
     println!("\n{}\n", "Session Details".bold());
-    println!("  {}: {}", "ID".dimmed(), session.id);
+    println!("  {}: {}", "ID".themed(Role::Muted), session.id);
     println!(
         "  {}: {}",
-        "Title".dimmed(),
+        "Title".themed(Role::Muted),
         session.title.as_deref().unwrap_or("(untitled)").bold()
     );
     println!(
         "  {}: {}",
-        "Status".dimmed(),
+        "Status".themed(Role::Muted),
         status_colored(session.session_status.as_deref().unwrap_or("unknown"))
     );
     println!(
         "  {}: {}",
-        "Type".dimmed(),
+        "Type".themed(Role::Muted),
         session.session_type.as_deref().unwrap_or("unknown")
     );
     println!(
         "  {}: {}",
-        "Created".dimmed(),
+        "Created".themed(Role::Muted),
         session
             .created_at
             .as_deref()
@@ -89,7 +444,7 @@ pub fn print_session_detail(session: &Session) {
     );
     println!(
         "  {}: {}",
-        "Updated".dimmed(),
+        "Updated".themed(Role::Muted),
         session
             .updated_at
             .as_deref()
@@ -100,15 +455,18 @@ pub fn print_session_detail(session: &Session) {
     if let Some(ref ctx) = session.session_context {
         println!(
             "  {}: {}",
-            "Model".dimmed(),
-            ctx.model.as_deref().unwrap_or("unknown").cyan()
+            "Model".themed(Role::Muted),
+            ctx.model
+                .as_deref()
+                .unwrap_or("unknown")
+                .themed(Role::Accent)
         );
 
         if let Some(ref sources) = ctx.sources {
             for src in sources {
                 println!(
                     "  {}: {} ({})",
-                    "Source".dimmed(),
+                    "Source".themed(Role::Muted),
                     src.url.as_deref().unwrap_or(""),
                     src.revision.as_deref().unwrap_or("")
                 );
@@ -120,87 +478,217 @@ pub fn print_session_detail(session: &Session) {
                 if let Some(ref git) = out.git_info {
                     println!(
                         "  {}: {}",
-                        "Repo".dimmed(),
+                        "Repo".themed(Role::Muted),
                         git.repo.as_deref().unwrap_or("")
                     );
                     if let Some(ref branches) = git.branches {
                         for b in branches {
-                            println!("  {}: {}", "Branch".dimmed(), b.green());
+                            println!(
+                                "  {}: {}",
+                                "Branch".themed(Role::Muted),
+                                b.themed(Role::Success)
+                            );
                         }
                     }
                 }
             }
         }
+
+        for line in change_lines(ctx) {
+            println!("  {}: {}", "Change".themed(Role::Muted), line);
+        }
+
+        for (label, tools) in [
+            ("Allowed tools", &ctx.allowed_tools),
+            ("Disallowed tools", &ctx.disallowed_tools),
+        ] {
+            if let Some(tools) = tools.as_ref().filter(|t| !t.is_empty()) {
+                println!("  {}: {}", label.themed(Role::Muted), tools.join(", "));
+            }
+        }
+        if let Some(ids) = ctx.knowledge_base_ids.as_ref().filter(|k| !k.is_empty()) {
+            println!(
+                "  {}: {}",
+                "Knowledge bases".themed(Role::Muted),
+                format_id_count(ids)
+            );
+        }
+    }
+
+    if full {
+        print_session_extras(session);
     }
 
     println!(
         "\n  {} claude --teleport {}\n",
-        "Resume with:".dimmed(),
-        session.id.cyan()
+        "Resume with:".themed(Role::Muted),
+        session.id.themed(Role::Accent)
     );
 }
 
-pub fn print_event(event: &SessionEvent) {
-    let created = event.created_at().map(format_timestamp).unwrap_or_default();
+/// Applies `styled`'s colors unless `opts.plain` asks for bare text.
+fn paint(styled: ColoredString, opts: &DisplayOptions) -> String {
+    if opts.plain {
+        styled.input
+    } else {
+        styled.to_string()
+    }
+}
 
-    match event {
-        SessionEvent::System(e) => {
-            let subtype = e.subtype.as_deref().unwrap_or("");
-            let model = e.model.as_deref().unwrap_or("");
-            let cwd = e.cwd.as_deref().unwrap_or("");
-            println!(
-                "{} {} [{}] model={} cwd={}",
-                created.dimmed(),
-                "SYSTEM".magenta().bold(),
-                subtype,
-                model.cyan(),
-                cwd
-            );
+/// Type label used by both the full and the compact rendering.
+fn event_label(event: &SessionEvent, opts: &DisplayOptions) -> String {
+    let label = match event {
+        SessionEvent::System(_) => "SYSTEM".themed(Role::Highlight).bold(),
+        SessionEvent::User(_) => "USER".themed(Role::Success).bold(),
+        SessionEvent::Assistant(_) => "ASSISTANT".themed(Role::Info).bold(),
+        SessionEvent::ToolUseSummary(_) => "SUMMARY".themed(Role::Warning),
+        SessionEvent::ToolProgress(_) => "PROGRESS".themed(Role::Muted),
+        SessionEvent::Result(_) => "RESULT".themed(Role::Accent).bold(),
+        SessionEvent::ControlResponse(_) => "CONTROL".themed(Role::Muted),
+        SessionEvent::EnvManagerLog(_) => "ENV".themed(Role::Muted),
+        SessionEvent::Unknown => "UNKNOWN".themed(Role::Muted),
+    };
+    paint(label, opts)
+}
+
+/// Short single-line preview of an event's content for `--compact`.
+fn compact_preview(event: &SessionEvent, opts: &DisplayOptions) -> String {
+    let preview = match event {
+        SessionEvent::System(e) => format!(
+            "[{}] model={}",
+            e.subtype.as_deref().unwrap_or(""),
+            e.model.as_deref().unwrap_or("")
+        ),
+        SessionEvent::User(e) => match &e.message.content {
+            UserContent::Text(t) => t.clone(),
+            UserContent::Blocks(b) => {
+                let texts = e.message.content.texts();
+                if texts.is_empty() {
+                    format!("({} blocks)", b.len())
+                } else {
+                    texts.join(" ")
+                }
+            }
+        },
+        SessionEvent::Assistant(e) => e
+            .message
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text(t) => t.text.clone(),
+                _ if opts.text_only => None,
+                ContentBlock::ToolUse(t) => Some(format!(
+                    "tool_use: {}",
+                    t.name.as_deref().unwrap_or("unknown")
+                )),
+                ContentBlock::ToolResult(_) => Some("tool_result".to_string()),
+                ContentBlock::Thinking(_) | ContentBlock::Other => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" | "),
+        SessionEvent::ToolUseSummary(e) => e.summary.clone().unwrap_or_default(),
+        SessionEvent::ToolProgress(e) => format!(
+            "{} ({})",
+            e.tool_name.as_deref().unwrap_or(""),
+            format_duration(e.elapsed_time_seconds.unwrap_or(0) * 1000)
+        ),
+        SessionEvent::Result(e) => {
+            format!("duration={}", format_duration(e.duration_ms.unwrap_or(0)))
         }
+        SessionEvent::ControlResponse(e) => e
+            .response
+            .as_ref()
+            .and_then(|r| r.subtype.clone())
+            .unwrap_or_default(),
+        SessionEvent::EnvManagerLog(e) => match &e.data {
+            Some(d) => format!(
+                "[{}] {}",
+                d.level.as_deref().unwrap_or("info"),
+                d.content.as_deref().unwrap_or("")
+            ),
+            None => String::new(),
+        },
+        SessionEvent::Unknown => String::new(),
+    };
+    let single_line = preview.split_whitespace().collect::<Vec<_>>().join(" ");
+    opts.preview(&single_line, 100)
+}
+
+/// Renders an event as `read` shows it: one line in compact mode, otherwise
+/// a header line followed by indented content. User and assistant messages
+/// end with a blank line. In raw mode, the event's JSON on one line.
+pub fn render_event(event: &SessionEvent, opts: &DisplayOptions) -> String {
+    if opts.raw {
+        return serde_json::to_string(event).unwrap_or_default();
+    }
+    let timestamp = event.created_at().map(|ts| match opts.relative_to {
+        Some(now) => format_relative(ts, now),
+        None => format_timestamp(ts),
+    });
+    let mut created = paint(timestamp.unwrap_or_default().themed(Role::Muted), opts);
+    if opts.show_uuid
+        && let Some(uuid) = event.uuid()
+    {
+        let short: String = uuid.chars().take(8).collect();
+        created = format!("{} {created}", paint(short.themed(Role::Warning), opts));
+    }
+    let label = event_label(event, opts);
+
+    if opts.compact {
+        return format!("{created} {label} {}", compact_preview(event, opts));
+    }
+
+    let mut lines = Vec::new();
+    match event {
+        SessionEvent::System(e) => lines.push(format!(
+            "{created} {label} [{}] model={} cwd={}",
+            e.subtype.as_deref().unwrap_or(""),
+            paint(e.model.as_deref().unwrap_or("").themed(Role::Accent), opts),
+            e.cwd.as_deref().unwrap_or("")
+        )),
 
         SessionEvent::User(e) => {
-            let content = e.message.content.as_text().unwrap_or("");
-            println!("{} {}", created.dimmed(), "USER".green().bold());
-            for line in content.lines() {
-                println!("  {line}");
+            lines.push(format!("{created} {label}"));
+            match &e.message.content {
+                UserContent::Text(text) => lines.extend(indented_lines(text, opts)),
+                UserContent::Blocks(blocks) => {
+                    for block in blocks {
+                        lines.extend(render_user_block(block, opts));
+                    }
+                }
             }
-            println!();
+            lines.push(String::new());
         }
 
         SessionEvent::Assistant(e) => {
-            println!("{} {}", created.dimmed(), "ASSISTANT".blue().bold());
+            lines.push(format!("{created} {label}"));
             for block in &e.message.content {
-                print_content_block(block);
+                let rendered = render_content_block(block, opts);
+                if !rendered.is_empty() {
+                    lines.push(rendered);
+                }
             }
-            println!();
-        }
-
-        SessionEvent::ToolUseSummary(e) => {
-            let summary = e.summary.as_deref().unwrap_or("");
-            println!("{} {} {}", created.dimmed(), "SUMMARY".yellow(), summary);
+            lines.push(String::new());
         }
 
-        SessionEvent::ToolProgress(e) => {
-            let tool = e.tool_name.as_deref().unwrap_or("");
-            let elapsed = e.elapsed_time_seconds.unwrap_or(0);
-            println!(
-                "{} {} {} ({}s)",
-                created.dimmed(),
-                "PROGRESS".dimmed(),
-                tool.dimmed(),
-                elapsed,
-            );
-        }
-
-        SessionEvent::Result(e) => {
-            let duration_s = e.duration_ms.unwrap_or(0) / 1000;
-            println!(
-                "{} {} duration={}s",
-                created.dimmed(),
-                "RESULT".cyan().bold(),
-                duration_s,
-            );
-        }
+        SessionEvent::ToolUseSummary(e) => lines.push(format!(
+            "{created} {label} {}",
+            e.summary.as_deref().unwrap_or("")
+        )),
+
+        SessionEvent::ToolProgress(e) => lines.push(format!(
+            "{created} {label} {} ({})",
+            paint(
+                e.tool_name.as_deref().unwrap_or("").themed(Role::Muted),
+                opts
+            ),
+            format_duration(e.elapsed_time_seconds.unwrap_or(0) * 1000),
+        )),
+
+        SessionEvent::Result(e) => lines.push(format!(
+            "{created} {label} duration={}",
+            format_duration(e.duration_ms.unwrap_or(0)),
+        )),
 
         SessionEvent::ControlResponse(e) => {
             let subtype = e
@@ -208,12 +696,10 @@ pub fn print_event(event: &SessionEvent) {
                 .as_ref()
                 .and_then(|r| r.subtype.as_deref())
                 .unwrap_or("");
-            println!(
-                "{} {} [{}]",
-                created.dimmed(),
-                "CONTROL".dimmed(),
-                subtype.dimmed()
-            );
+            lines.push(format!(
+                "{created} {label} [{}]",
+                paint(subtype.themed(Role::Muted), opts)
+            ));
         }
 
         SessionEvent::EnvManagerLog(e) => {
@@ -225,121 +711,390 @@ pub fn print_event(event: &SessionEvent) {
                 None => ("", "info"),
             };
             let level_colored = match level {
-                "error" => level.red().to_string(),
-                "warn" => level.yellow().to_string(),
-                "debug" => level.dimmed().to_string(),
-                _ => level.to_string(),
+                "error" => level.themed(Role::Error),
+                "warn" => level.themed(Role::Warning),
+                "debug" => level.themed(Role::Muted),
+                _ => level.normal(),
             };
-            println!(
-                "{} {} [{}] {}",
-                created.dimmed(),
-                "ENV".dimmed(),
-                level_colored,
-                content
-            );
+            lines.push(format!(
+                "{created} {label} [{}] {content}",
+                paint(level_colored, opts)
+            ));
         }
 
-        SessionEvent::Unknown => {
-            println!("{} {}", created.dimmed(), "UNKNOWN".dimmed());
-        }
+        SessionEvent::Unknown => lines.push(format!("{created} {label}")),
     }
+    lines.join("\n")
 }
 
-fn print_content_block(block: &ContentBlock) {
-    match block {
-        ContentBlock::Thinking(b) => {
-            if let Some(ref text) = b.thinking
-                && !text.is_empty()
-            {
-                let preview = truncate_str(text, 200);
-                println!("  {} {}", "thinking:".dimmed(), preview.dimmed());
-            }
+pub fn print_event(event: &SessionEvent, opts: &DisplayOptions) {
+    println!("{}", render_event(event, opts));
+}
+
+/// Remembers when the last printed event happened, for `--annotate-gaps`.
+#[derive(Debug, Default)]
+pub struct GapAnnotator {
+    /// Gaps longer than this many seconds get a separator; `None` disables it.
+    threshold_secs: Option<u64>,
+    last: Option<DateTime<Utc>>,
+}
+
+impl GapAnnotator {
+    pub fn new(threshold_secs: Option<u64>) -> Self {
+        Self {
+            threshold_secs,
+            last: None,
         }
-        ContentBlock::Text(b) => {
-            let text = b.text.as_deref().unwrap_or("");
-            for line in text.lines() {
-                println!("  {line}");
-            }
+    }
+
+    /// The "⏱ 2m 14s gap" line to print before `event` when it is more than
+    /// the threshold from the previous timestamped event, in either direction
+    /// so reversed transcripts work too. Events without a timestamp neither
+    /// get a separator nor reset the clock.
+    pub fn separator(&mut self, event: &SessionEvent, opts: &DisplayOptions) -> Option<String> {
+        let threshold_secs = self.threshold_secs?;
+        let at = event.created_at()?.parse::<DateTime<Utc>>().ok()?;
+        let previous = self.last.replace(at)?;
+        let gap_ms = (at - previous).num_milliseconds().unsigned_abs();
+        if gap_ms <= threshold_secs.saturating_mul(1000) {
+            return None;
         }
+        let line = format!("\u{23f1} {} gap", format_duration(gap_ms));
+        Some(format!("  {}", paint(line.themed(Role::Muted), opts)))
+    }
+}
+
+/// Renders one assistant content block as indented lines; empty for blocks
+/// with nothing to show.
+pub fn render_content_block(block: &ContentBlock, opts: &DisplayOptions) -> String {
+    if opts.text_only && !matches!(block, ContentBlock::Text(_)) {
+        return String::new();
+    }
+    let lines = match block {
+        ContentBlock::Thinking(b) => match b.thinking.as_deref() {
+            Some(text) if !text.is_empty() => {
+                let preview = opts.preview(text, 200);
+                vec![format!(
+                    "  {} {}",
+                    paint("thinking:".themed(Role::Muted), opts),
+                    paint(preview.themed(Role::Muted), opts)
+                )]
+            }
+            _ => Vec::new(),
+        },
+        ContentBlock::Text(b) => indented_lines(b.text.as_deref().unwrap_or(""), opts),
         ContentBlock::ToolUse(b) => {
             let tool = b.name.as_deref().unwrap_or("unknown");
+            if let Some(diff) = b
+                .input
+                .as_ref()
+                .and_then(|input| render_edit_diff(tool, input, opts))
+            {
+                return diff.join("\n");
+            }
+            if opts.pretty_input
+                && let Some(input) = &b.input
+            {
+                return render_pretty_input(tool, input, opts).join("\n");
+            }
             let input_preview = b
                 .input
                 .as_ref()
                 .map(|v| {
                     let s = serde_json::to_string(v).unwrap_or_default();
-                    truncate_str(&s, 120)
+                    opts.preview(&s, 120)
                 })
                 .unwrap_or_default();
-            println!(
+            vec![format!(
                 "  {} {} {}",
-                "tool_use:".yellow(),
-                tool.cyan().bold(),
-                input_preview.dimmed()
-            );
+                paint("tool_use:".themed(Role::Warning), opts),
+                paint(tool.themed(Role::Accent).bold(), opts),
+                paint(input_preview.themed(Role::Muted), opts)
+            )]
         }
         ContentBlock::ToolResult(b) => {
-            let preview = b
-                .content
-                .as_ref()
-                .map(|v| {
-                    let s = serde_json::to_string(v).unwrap_or_default();
-                    truncate_str(&s, 200)
-                })
-                .unwrap_or_default();
-            println!("  {} {}", "tool_result:".yellow(), preview.dimmed());
+            render_tool_result(b.content.as_ref(), b.is_error == Some(true), opts)
+        }
+        ContentBlock::Other => Vec::new(),
+    };
+    lines.join("\n")
+}
+
+/// Longest string value kept whole in `--pretty-input` unless `full_results` is set.
+const PRETTY_INPUT_MAX_CHARS: usize = 300;
+
+/// Renders a tool call with its input pretty-printed as JSON below the tool line.
+/// Long string values are cut one by one, so every field stays visible.
+fn render_pretty_input(
+    tool: &str,
+    input: &serde_json::Value,
+    opts: &DisplayOptions,
+) -> Vec<String> {
+    fn shorten(v: &serde_json::Value, max: usize) -> serde_json::Value {
+        match v {
+            serde_json::Value::String(s) => serde_json::Value::String(truncate_str(s, max)),
+            serde_json::Value::Array(items) => items.iter().map(|i| shorten(i, max)).collect(),
+            serde_json::Value::Object(map) => map
+                .iter()
+                .map(|(k, v)| (k.clone(), shorten(v, max)))
+                .collect(),
+            other => other.clone(),
         }
-        ContentBlock::Other => {}
     }
+    let max = opts.truncate.unwrap_or(PRETTY_INPUT_MAX_CHARS);
+    let pretty = if opts.full_results || max == 0 {
+        serde_json::to_string_pretty(input)
+    } else {
+        serde_json::to_string_pretty(&shorten(input, max))
+    }
+    .unwrap_or_default();
+
+    let mut lines = vec![format!(
+        "  {} {}",
+        paint("tool_use:".themed(Role::Warning), opts),
+        paint(tool.themed(Role::Accent).bold(), opts)
+    )];
+    lines.extend(
+        pretty
+            .lines()
+            .map(|line| format!("    {}", paint(line.themed(Role::Muted), opts))),
+    );
+    lines
 }
 
-#[cfg(test)]
-fn format_content_block(block: &ContentBlock) -> String {
-    let mut lines = Vec::new();
-    match block {
-        ContentBlock::Thinking(b) => {
-            if let Some(ref text) = b.thinking
-                && !text.is_empty()
-            {
-                let preview = truncate_str(text, 200);
-                lines.push(format!("  thinking: {preview}"));
-            }
+/// Lines shown per side of an edit diff unless `full_results` is set.
+const DIFF_PREVIEW_LINES: usize = 20;
+
+/// Renders the input of the file-editing tools (Edit, MultiEdit, Write) as a
+/// diff: removed text as red `-` lines, added text as green `+` lines.
+/// Returns `None` for other tools or unexpected input.
+fn render_edit_diff(
+    tool: &str,
+    input: &serde_json::Value,
+    opts: &DisplayOptions,
+) -> Option<Vec<String>> {
+    fn field<'a>(v: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+        v.get(key).and_then(|s| s.as_str())
+    }
+    fn edit(v: &serde_json::Value) -> Option<(&str, &str)> {
+        Some((
+            field(v, "old_string").unwrap_or(""),
+            field(v, "new_string")?,
+        ))
+    }
+    let hunks: Vec<(&str, &str)> = match tool {
+        "Edit" => vec![edit(input)?],
+        "MultiEdit" => input
+            .get("edits")?
+            .as_array()?
+            .iter()
+            .map(edit)
+            .collect::<Option<_>>()?,
+        "Write" => vec![("", field(input, "content")?)],
+        _ => return None,
+    };
+
+    let mut lines = vec![format!(
+        "  {} {} {}",
+        paint("tool_use:".themed(Role::Warning), opts),
+        paint(tool.themed(Role::Accent).bold(), opts),
+        paint(
+            field(input, "file_path").unwrap_or("").themed(Role::Muted),
+            opts
+        )
+    )];
+    for (i, (old, new)) in hunks.iter().enumerate() {
+        if i > 0 {
+            lines.push(paint("    ...".themed(Role::Muted), opts));
         }
-        ContentBlock::Text(b) => {
-            let text = b.text.as_deref().unwrap_or("");
-            for line in text.lines() {
-                lines.push(format!("  {line}"));
-            }
+        lines.extend(diff_lines(old, '-', opts));
+        lines.extend(diff_lines(new, '+', opts));
+    }
+    Some(lines)
+}
+
+/// One side of an edit diff, each line prefixed with `marker`.
+fn diff_lines(text: &str, marker: char, opts: &DisplayOptions) -> Vec<String> {
+    let limit = if opts.full_results {
+        usize::MAX
+    } else {
+        DIFF_PREVIEW_LINES
+    };
+    let color = |line: String| {
+        if marker == '-' {
+            line.themed(Role::Error)
+        } else {
+            line.themed(Role::Success)
         }
-        ContentBlock::ToolUse(b) => {
-            let tool = b.name.as_deref().unwrap_or("unknown");
-            let input_preview = b
-                .input
-                .as_ref()
-                .map(|v| {
-                    let s = serde_json::to_string(v).unwrap_or_default();
-                    truncate_str(&s, 120)
-                })
-                .unwrap_or_default();
-            lines.push(format!("  tool_use: {tool} {input_preview}"));
+    };
+    let mut lines: Vec<String> = text
+        .lines()
+        .take(limit)
+        .map(|line| paint(color(format!("    {marker} {line}")), opts))
+        .collect();
+    let total = text.lines().count();
+    if total > limit {
+        lines.push(paint(
+            format!("    {marker} ... {} more lines", total - limit).themed(Role::Muted),
+            opts,
+        ));
+    }
+    lines
+}
+
+/// Failed results get a red `✗ tool_result:` label, successful ones green.
+fn render_tool_result(
+    content: Option<&serde_json::Value>,
+    is_error: bool,
+    opts: &DisplayOptions,
+) -> Vec<String> {
+    let label = if is_error {
+        paint("\u{2717} tool_result:".themed(Role::Error), opts)
+    } else {
+        paint("tool_result:".themed(Role::Success), opts)
+    };
+    if opts.full_results {
+        let mut lines = vec![format!("  {label}")];
+        if let Some(content) = content {
+            lines.extend(indented_lines(&format_tool_result_full(content), opts));
         }
-        ContentBlock::ToolResult(b) => {
-            let preview = b
-                .content
-                .as_ref()
-                .map(|v| {
-                    let s = serde_json::to_string(v).unwrap_or_default();
-                    truncate_str(&s, 200)
-                })
-                .unwrap_or_default();
-            lines.push(format!("  tool_result: {preview}"));
+        return lines;
+    }
+    let preview = content
+        .map(|v| {
+            let s = match v.as_str() {
+                Some(text) if opts.trim => serde_json::to_string(&trim_blank_runs(text)),
+                _ => serde_json::to_string(v),
+            }
+            .unwrap_or_default();
+            opts.preview(&s, 200)
+        })
+        .unwrap_or_default();
+    vec![format!(
+        "  {label} {}",
+        paint(preview.themed(Role::Muted), opts)
+    )]
+}
+
+/// Renders one raw block of a block-form user message. Text and tool results
+/// are shown; anything else (images, documents) is noted by its type.
+fn render_user_block(block: &serde_json::Value, opts: &DisplayOptions) -> Vec<String> {
+    let block_type = block
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("unknown");
+    match block_type {
+        "text" => indented_lines(
+            block.get("text").and_then(|t| t.as_str()).unwrap_or(""),
+            opts,
+        ),
+        "tool_result" => render_tool_result(
+            block.get("content"),
+            block.get("is_error").and_then(|e| e.as_bool()) == Some(true),
+            opts,
+        ),
+        other => vec![format!(
+            "  {}",
+            paint(format!("[{other}]").themed(Role::Muted), opts)
+        )],
+    }
+}
+
+/// Renders tool_result content untruncated: strings keep their newlines,
+/// arrays and objects are pretty-printed.
+fn format_tool_result_full(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            serde_json::to_string_pretty(content).unwrap_or_default()
         }
-        ContentBlock::Other => {}
+        other => other.to_string(),
     }
-    lines.join("\n")
 }
 
-pub fn print_logline(log: &Logline) {
+pub fn print_event_schema(schema: &EventSchema) {
+    println!(
+        "\n{} ({})\n",
+        schema.event_type.bold(),
+        schema.rust_type.themed(Role::Muted)
+    );
+    let width = schema
+        .fields
+        .iter()
+        .map(|f| f.json.len())
+        .max()
+        .unwrap_or(0);
+    for f in schema.fields {
+        let rust = if f.rust == f.json {
+            String::new()
+        } else {
+            format!(" (rust: {})", f.rust)
+        };
+        println!(
+            "  {:width$}  {}{}",
+            f.json.themed(Role::Accent),
+            f.ty.themed(Role::Muted),
+            rust.themed(Role::Muted)
+        );
+    }
+    println!();
+}
+
+/// Lines for a bucket's totals and, with `per_tool`, a calls/errors/time row
+/// for each tool in it.
+pub fn stats_bucket_lines(bucket: &Bucket, per_tool: bool) -> Vec<String> {
+    let mut lines = vec![format!(
+        "  {}  {}",
+        bucket.key.bold(),
+        format_tool_stats(&bucket.total).themed(Role::Muted)
+    )];
+    if !per_tool {
+        return lines;
+    }
+    let width = bucket.by_tool.keys().map(|k| k.len()).max().unwrap_or(0);
+    let mut tools: Vec<_> = bucket.by_tool.iter().collect();
+    tools.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then(a.0.cmp(b.0)));
+    for (tool, stats) in tools {
+        lines.push(format!("    {tool:width$}  {}", format_tool_stats(stats)));
+    }
+    lines
+}
+
+/// One `stats` line per slow call: duration, tool, time, and tool_use ID.
+/// Calls over `threshold_secs` are shown as errors.
+pub fn slow_call_lines(rows: &[&ToolUsageRow], threshold_secs: Option<u64>) -> Vec<String> {
+    let width = rows.iter().map(|r| r.tool_name.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|row| {
+            let secs = row.elapsed_seconds.unwrap_or(0);
+            let duration = format!("{:>8}", format_duration(secs * 1000));
+            let duration = if threshold_secs.is_some_and(|t| secs > t) {
+                duration.themed(Role::Error).bold()
+            } else {
+                duration.themed(Role::Accent)
+            };
+            format!(
+                "  {duration}  {:width$}  {}  {}",
+                row.tool_name,
+                format_timestamp(&row.timestamp).themed(Role::Muted),
+                row.tool_use_id.themed(Role::Muted)
+            )
+        })
+        .collect()
+}
+
+fn format_tool_stats(stats: &ToolStats) -> String {
+    format!(
+        "{} calls, {} errors, {}",
+        stats.calls,
+        stats.errors,
+        format_duration(stats.elapsed_seconds * 1000)
+    )
+}
+
+/// Renders one logline: a header with time, type, and branch, then the content,
+/// cut to 200 chars unless `opts.full_results` (then wrapped per `opts.wrap`).
+pub fn render_logline(log: &Logline, opts: &DisplayOptions) -> String {
     let log_type = log.log_type.as_deref().unwrap_or("unknown");
     let subtype = log.subtype.as_deref().unwrap_or("");
     let content = log.content.as_deref().unwrap_or("");
@@ -356,24 +1111,36 @@ pub fn print_logline(log: &Logline) {
         format!("{log_type}/{subtype}")
     };
 
-    let type_colored = match log_type {
-        "system" => type_display.magenta().to_string(),
-        "user" => type_display.green().to_string(),
-        "assistant" => type_display.blue().to_string(),
-        _ => type_display.dimmed().to_string(),
-    };
+    let type_colored = paint(
+        match log_type {
+            "system" => type_display.themed(Role::Highlight),
+            "user" => type_display.themed(Role::Success),
+            "assistant" => type_display.themed(Role::Info),
+            _ => type_display.themed(Role::Muted),
+        },
+        opts,
+    );
 
-    println!(
-        "{} {} {}",
-        timestamp.dimmed(),
+    let mut out = format!(
+        "{} {} {}\n",
+        paint(timestamp.themed(Role::Muted), opts),
         type_colored,
-        branch.dimmed()
+        paint(branch.themed(Role::Muted), opts)
     );
-    if !content.is_empty() {
+    if opts.full_results {
+        for line in indented_lines(content, opts) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    } else if !content.is_empty() {
         let preview: String = content.chars().take(200).collect();
-        println!("  {preview}");
+        out.push_str(&format!("  {preview}\n"));
     }
-    println!();
+    out
+}
+
+pub fn print_logline(log: &Logline, opts: &DisplayOptions) {
+    println!("{}", render_logline(log, opts));
 }
 
 #[cfg(test)]
@@ -428,6 +1195,102 @@ mod tests {
         assert_eq!(truncate_str("", 10), "");
     }
 
+    // ── wrap_line ───────────────────────────────────────────────────
+
+    #[test]
+    fn wrap_line_disabled() {
+        let line = "a ".repeat(100);
+        assert_eq!(wrap_line(&line, 2, 0), vec![line.clone()]);
+    }
+
+    #[test]
+    fn wrap_line_short_line_untouched() {
+        assert_eq!(wrap_line("hello world", 2, 80), vec!["hello world"]);
+    }
+
+    #[test]
+    fn wrap_line_breaks_at_spaces() {
+        let lines = wrap_line("the quick brown fox jumps", 2, 14);
+        assert_eq!(lines, vec!["the quick", "brown fox", "jumps"]);
+        assert!(lines.iter().all(|l| l.chars().count() <= 12));
+    }
+
+    #[test]
+    fn wrap_line_hard_breaks_long_words() {
+        let lines = wrap_line("abcdefghij", 0, 4);
+        assert_eq!(lines, vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn wrap_line_preserves_indentation() {
+        let lines = wrap_line("    let x = some_function(arg1, arg2);", 2, 24);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.starts_with("    ")));
+        assert!(lines.iter().all(|l| l.chars().count() <= 22));
+    }
+
+    #[test]
+    fn wrap_line_multibyte_no_panic() {
+        let line = "─".repeat(50);
+        let lines = wrap_line(&line, 2, 12);
+        assert_eq!(lines.concat(), line);
+    }
+
+    // ── trim_blank_runs ─────────────────────────────────────────────
+
+    #[test]
+    fn trim_blank_runs_collapses_three_or_more() {
+        assert_eq!(trim_blank_runs("a  \n\n\n\n\tb\t\n"), "a\n\n\tb");
+        assert_eq!(trim_blank_runs("a\n \n\nb"), "a\n\n\nb");
+        assert_eq!(trim_blank_runs("a\n\n\n\n\n"), "a\n");
+    }
+
+    #[test]
+    fn trim_option_applies_to_text_and_tool_results() {
+        let opts = DisplayOptions {
+            trim: true,
+            ..Default::default()
+        };
+        let text = ContentBlock::Text(TextBlock {
+            text: Some("one   \n\n\n\n\ntwo".to_string()),
+        });
+        assert_eq!(render_content_block(&text, &opts), "  one\n  \n  two");
+
+        let result = ContentBlock::ToolResult(ToolResultBlock {
+            tool_use_id: None,
+            content: Some(json!("out  \n\n\n\nend")),
+            is_error: None,
+        });
+        assert!(render_content_block(&result, &opts).contains(r#""out\n\nend""#));
+    }
+
+    // ── format_bytes ────────────────────────────────────────────────
+
+    #[test]
+    fn format_bytes_small() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+    }
+
+    #[test]
+    fn format_bytes_binary_units() {
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(3_565_158), "3.4 MiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    // ── format_duration ─────────────────────────────────────────────
+
+    #[test]
+    fn format_duration_units() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(850), "850ms");
+        assert_eq!(format_duration(42_000), "42s");
+        assert_eq!(format_duration(930_000), "15m 30s");
+        assert_eq!(format_duration(3_723_000), "1h 2m 3s");
+        assert_eq!(format_duration(7_200_999), "2h");
+    }
+
     // ── format_timestamp ────────────────────────────────────────────
 
     #[test]
@@ -494,7 +1357,15 @@ mod tests {
         assert!(result.contains("something_else"));
     }
 
-    // ── format_content_block ────────────────────────────────────────
+    // ── render_content_block ────────────────────────────────────────
+
+    fn format_content_block(block: &ContentBlock) -> String {
+        let opts = DisplayOptions {
+            plain: true,
+            ..Default::default()
+        };
+        render_content_block(block, &opts)
+    }
 
     #[test]
     fn format_thinking_block() {
@@ -576,8 +1447,8 @@ mod tests {
         let long_input = "x".repeat(200);
         let block = ContentBlock::ToolUse(ToolUseBlock {
             id: None,
-            name: Some("Write".to_string()),
-            input: Some(json!({"content": long_input})),
+            name: Some("Bash".to_string()),
+            input: Some(json!({"command": long_input})),
         });
         let output = format_content_block(&block);
         assert!(output.contains("..."));
@@ -602,7 +1473,102 @@ mod tests {
         assert!(output.contains("Edit"));
     }
 
-    /// Ensure print_content_block (the actual println path) also doesn't panic with Unicode.
+    // ── render_edit_diff ────────────────────────────────────────────
+
+    fn tool_use(name: &str, input: serde_json::Value) -> ContentBlock {
+        ContentBlock::ToolUse(ToolUseBlock {
+            id: None,
+            name: Some(name.to_string()),
+            input: Some(input),
+        })
+    }
+
+    #[test]
+    fn edit_input_renders_as_diff() {
+        let block = tool_use(
+            "Edit",
+            json!({ "file_path": "/src/lib.rs", "old_string": "a\nb", "new_string": "c" }),
+        );
+        assert_eq!(
+            format_content_block(&block),
+            "  tool_use: Edit /src/lib.rs\n    - a\n    - b\n    + c"
+        );
+    }
+
+    #[test]
+    fn multi_edit_separates_hunks() {
+        let block = tool_use(
+            "MultiEdit",
+            json!({ "file_path": "/f", "edits": [
+                { "old_string": "x", "new_string": "y" },
+                { "old_string": "p", "new_string": "q" }
+            ] }),
+        );
+        assert_eq!(
+            format_content_block(&block),
+            "  tool_use: MultiEdit /f\n    - x\n    + y\n    ...\n    - p\n    + q"
+        );
+    }
+
+    #[test]
+    fn write_input_shows_added_lines_capped() {
+        let content = (1..=25)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let block = tool_use("Write", json!({ "file_path": "/f", "content": content }));
+        let output = format_content_block(&block);
+        assert!(output.contains("    + 20\n    + ... 5 more lines"));
+        assert!(!output.contains("+ 21"));
+
+        let full = DisplayOptions {
+            plain: true,
+            full_results: true,
+            ..Default::default()
+        };
+        assert!(render_content_block(&block, &full).ends_with("    + 25"));
+    }
+
+    #[test]
+    fn edit_diff_falls_back_to_json_for_unexpected_input() {
+        let block = tool_use("Edit", json!({ "file_path": "/f" }));
+        assert_eq!(
+            format_content_block(&block),
+            "  tool_use: Edit {\"file_path\":\"/f\"}"
+        );
+        let other = tool_use("Read", json!({ "file_path": "/f" }));
+        assert!(format_content_block(&other).contains("{\"file_path\""));
+    }
+
+    #[test]
+    fn pretty_input_indents_json_and_truncates_per_field() {
+        let long = "y".repeat(PRETTY_INPUT_MAX_CHARS + 50);
+        let block = tool_use("Bash", json!({ "command": long, "timeout": 5 }));
+        let opts = DisplayOptions {
+            plain: true,
+            pretty_input: true,
+            ..Default::default()
+        };
+        let output = render_content_block(&block, &opts);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "  tool_use: Bash");
+        assert_eq!(lines[1], "    {");
+        let command = format!(
+            "      \"command\": \"{}...\",",
+            "y".repeat(PRETTY_INPUT_MAX_CHARS)
+        );
+        assert_eq!(lines[2], command);
+        assert_eq!(lines[3], "      \"timeout\": 5");
+        assert_eq!(lines[4], "    }");
+
+        let full = DisplayOptions {
+            full_results: true,
+            ..opts
+        };
+        assert!(render_content_block(&block, &full).contains(&long));
+    }
+
+    /// Ensure the colored rendering used by `read` also doesn't panic with Unicode.
     #[test]
     fn print_tool_use_unicode_no_panic() {
         let content = format!("// {}", "─".repeat(100));
@@ -611,7 +1577,8 @@ mod tests {
             name: Some("Write".to_string()),
             input: Some(json!({"file_path": "/test.rs", "content": content})),
         });
-        print_content_block(&block);
+        let output = render_content_block(&block, &DisplayOptions::default());
+        assert!(output.contains("Write"));
     }
 
     #[test]
@@ -626,6 +1593,24 @@ mod tests {
         assert!(output.contains("result text"));
     }
 
+    #[test]
+    fn failed_tool_result_is_marked() {
+        let block = |is_error| {
+            ContentBlock::ToolResult(ToolResultBlock {
+                tool_use_id: None,
+                content: Some(json!("exit 1")),
+                is_error,
+            })
+        };
+        assert!(format_content_block(&block(Some(true))).contains("\u{2717} tool_result:"));
+        assert!(!format_content_block(&block(Some(false))).contains('\u{2717}'));
+        assert!(!format_content_block(&block(None)).contains('\u{2717}'));
+
+        let user_block = json!({ "type": "tool_result", "content": "denied", "is_error": true });
+        let lines = render_user_block(&user_block, &DisplayOptions::default());
+        assert!(lines[0].contains("\u{2717} tool_result:"));
+    }
+
     #[test]
     fn format_tool_result_block_long_truncates() {
         let long_result = "y".repeat(300);
@@ -638,6 +1623,45 @@ mod tests {
         assert!(output.contains("..."));
     }
 
+    #[test]
+    fn truncate_option_overrides_preview_limits() {
+        let block = ContentBlock::ToolResult(ToolResultBlock {
+            tool_use_id: None,
+            content: Some(json!("y".repeat(300))),
+            is_error: None,
+        });
+        let short = DisplayOptions {
+            truncate: Some(10),
+            ..Default::default()
+        };
+        assert!(render_content_block(&block, &short).contains(&format!("{}...", "y".repeat(9))));
+
+        let unlimited = DisplayOptions {
+            truncate: Some(0),
+            ..Default::default()
+        };
+        let output = render_content_block(&block, &unlimited);
+        assert!(output.contains(&"y".repeat(300)));
+        assert!(!output.contains("..."));
+    }
+
+    #[test]
+    fn text_only_hides_non_text_blocks() {
+        let opts = DisplayOptions {
+            text_only: true,
+            ..Default::default()
+        };
+        let thinking = ContentBlock::Thinking(ThinkingBlock {
+            thinking: Some("hmm".to_string()),
+            signature: None,
+        });
+        assert!(render_content_block(&thinking, &opts).is_empty());
+        let text = ContentBlock::Text(TextBlock {
+            text: Some("The answer".to_string()),
+        });
+        assert!(render_content_block(&text, &opts).contains("The answer"));
+    }
+
     #[test]
     fn format_other_block() {
         let block = ContentBlock::Other;
@@ -645,6 +1669,423 @@ mod tests {
         assert!(output.is_empty());
     }
 
+    // ── format_tool_result_full ─────────────────────────────────────
+
+    #[test]
+    fn tool_result_full_string_keeps_newlines() {
+        let long = format!("line one\n{}", "x".repeat(500));
+        let out = format_tool_result_full(&serde_json::Value::String(long.clone()));
+        assert_eq!(out, long);
+    }
+
+    #[test]
+    fn tool_result_full_pretty_prints_arrays() {
+        let content = serde_json::json!([{ "type": "text", "text": "ok" }]);
+        let out = format_tool_result_full(&content);
+        assert!(out.starts_with("[\n  {"));
+        assert!(out.contains("\"text\": \"ok\""));
+    }
+
+    #[test]
+    fn tool_result_full_scalars() {
+        assert_eq!(format_tool_result_full(&serde_json::json!(42)), "42");
+        assert_eq!(format_tool_result_full(&serde_json::Value::Null), "null");
+    }
+
+    // ── render_event ────────────────────────────────────────────────
+
+    #[test]
+    fn render_event_show_uuid_prefixes_short_uuid() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "tool_use_summary",
+            "uuid": "3f2a9c1e-77b0-4d6a-9e1f-0c2b8d4e5a61",
+            "summary": "Ran tests"
+        }))
+        .unwrap();
+        let opts = DisplayOptions {
+            plain: true,
+            show_uuid: true,
+            ..Default::default()
+        };
+        assert_eq!(render_event(&event, &opts), "3f2a9c1e  SUMMARY Ran tests");
+        let compact = DisplayOptions {
+            compact: true,
+            ..opts.clone()
+        };
+        assert_eq!(
+            render_event(&event, &compact),
+            "3f2a9c1e  SUMMARY Ran tests"
+        );
+    }
+
+    #[test]
+    fn render_event_show_uuid_skips_events_without_one() {
+        let event: SessionEvent =
+            serde_json::from_value(json!({ "type": "result", "duration_ms": 1500 })).unwrap();
+        let opts = DisplayOptions {
+            plain: true,
+            show_uuid: true,
+            ..Default::default()
+        };
+        assert_eq!(render_event(&event, &opts), " RESULT duration=1s");
+    }
+
+    // ── branch_lines ────────────────────────────────────────────────
+
+    fn session_with_outcomes(outcomes: Vec<SessionOutcome>) -> Session {
+        Session {
+            id: "s1".to_string(),
+            session_context: Some(SessionContext {
+                outcomes: Some(outcomes),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn git_outcome(repo: &str, branches: &[&str]) -> SessionOutcome {
+        SessionOutcome {
+            outcome_type: Some("git".to_string()),
+            git_info: Some(GitInfo {
+                git_type: Some("push".to_string()),
+                repo: Some(repo.to_string()),
+                branches: Some(branches.iter().map(|b| b.to_string()).collect()),
+            }),
+        }
+    }
+
+    #[test]
+    fn branch_lines_lists_each_outcome() {
+        let session = session_with_outcomes(vec![
+            git_outcome("acme/api", &["main", "feature"]),
+            SessionOutcome {
+                outcome_type: Some("other".to_string()),
+                git_info: None,
+            },
+            git_outcome("acme/web", &[]),
+        ]);
+        let text = branch_lines(&session).join("\n");
+        assert!(text.contains("acme/api"));
+        assert!(text.contains("push"));
+        assert!(text.contains("- ") && text.contains("feature"));
+        assert!(text.contains("acme/web"));
+        assert!(text.contains("(none)"));
+        assert_eq!(text.matches("Repo").count(), 2);
+    }
+
+    // ── change_lines ────────────────────────────────────────────────
+
+    fn source(url: &str, revision: Option<&str>) -> SessionSource {
+        SessionSource {
+            source_type: Some("git_repository".to_string()),
+            url: Some(url.to_string()),
+            revision: revision.map(String::from),
+        }
+    }
+
+    #[test]
+    fn change_lines_pair_sources_with_outcomes_by_repo() {
+        let ctx = SessionContext {
+            sources: Some(vec![
+                source("https://github.com/acme/web.git", Some("9f8e7d6c5b4a")),
+                source("https://github.com/acme/api", Some("0123456789abcdef")),
+            ]),
+            outcomes: Some(vec![
+                git_outcome("acme/api", &["claude/fix-login", "claude/tests"]),
+                git_outcome("acme/web", &["claude/style"]),
+                git_outcome("acme/other", &["claude/x"]),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            change_lines(&ctx),
+            [
+                "from 0123456 → pushed claude/fix-login, claude/tests",
+                "from 9f8e7d6 → pushed claude/style",
+            ]
+        );
+    }
+
+    #[test]
+    fn change_lines_need_a_revision_and_a_branch() {
+        let single = |rev, branches: &[&str]| SessionContext {
+            sources: Some(vec![source("https://example.com/mirror", rev)]),
+            outcomes: Some(vec![git_outcome("acme/api", branches)]),
+            ..Default::default()
+        };
+        // With one source, it's the starting point even if the URLs differ.
+        assert_eq!(
+            change_lines(&single(Some("abcdef0123"), &["main"])),
+            ["from abcdef0 → pushed main"]
+        );
+        assert!(change_lines(&single(None, &["main"])).is_empty());
+        assert!(change_lines(&single(Some("abcdef0123"), &[])).is_empty());
+        assert!(change_lines(&SessionContext::default()).is_empty());
+    }
+
+    #[test]
+    fn branch_lines_without_outcomes() {
+        let text = branch_lines(&Session::default()).join("\n");
+        assert!(text.contains("No git outcomes recorded."));
+        let text = branch_lines(&session_with_outcomes(vec![])).join("\n");
+        assert!(text.contains("No git outcomes recorded."));
+    }
+
+    #[test]
+    fn render_event_raw_is_one_json_line() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "user",
+            "uuid": "u1",
+            "message": { "content": "multi\nline" }
+        }))
+        .unwrap();
+        let opts = DisplayOptions {
+            raw: true,
+            ..Default::default()
+        };
+        let out = render_event(&event, &opts);
+        assert_eq!(out.lines().count(), 1);
+        let back: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(back["uuid"], "u1");
+    }
+
+    // ── format_id_count ─────────────────────────────────────────────
+
+    #[test]
+    fn id_count_lists_ids() {
+        let ids = vec!["kb_1".to_string(), "kb_2".to_string()];
+        assert_eq!(format_id_count(&ids), "2 (kb_1, kb_2)");
+    }
+
+    // ── format_relative ─────────────────────────────────────────────
+
+    #[test]
+    fn format_relative_unit_boundaries() {
+        let now: DateTime<Utc> = "2025-01-10T12:00:00Z".parse().unwrap();
+        let cases = [
+            ("2025-01-10T12:00:00Z", "0s ago"),
+            ("2025-01-10T11:59:01Z", "59s ago"),
+            ("2025-01-10T11:59:00Z", "1m ago"),
+            ("2025-01-10T11:00:01Z", "59m ago"),
+            ("2025-01-10T11:00:00Z", "1h ago"),
+            ("2025-01-09T12:00:01Z", "23h ago"),
+            ("2025-01-09T12:00:00Z", "1d ago"),
+            ("2025-01-01T12:00:00Z", "9d ago"),
+            ("2025-01-10T12:03:00Z", "in 3m"),
+        ];
+        for (ts, expected) in cases {
+            assert_eq!(format_relative(ts, now), expected, "{ts}");
+        }
+    }
+
+    #[test]
+    fn format_relative_invalid_returns_original() {
+        assert_eq!(format_relative("yesterday", Utc::now()), "yesterday");
+    }
+
+    #[test]
+    fn render_event_relative_time() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "tool_use_summary",
+            "created_at": "2025-01-10T10:00:00Z",
+            "summary": "Built"
+        }))
+        .unwrap();
+        let opts = DisplayOptions {
+            plain: true,
+            relative_to: Some("2025-01-10T12:30:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(render_event(&event, &opts), "2h ago SUMMARY Built");
+    }
+
+    // ── GapAnnotator ────────────────────────────────────────────────
+
+    #[test]
+    fn gap_annotator_marks_gaps_over_threshold() {
+        let at = |ts: Option<&str>| -> SessionEvent {
+            serde_json::from_value(json!({
+                "type": "tool_use_summary",
+                "created_at": ts,
+                "summary": "x"
+            }))
+            .unwrap()
+        };
+        let opts = DisplayOptions {
+            plain: true,
+            ..Default::default()
+        };
+        let mut gaps = GapAnnotator::new(Some(60));
+        let lines: Vec<Option<String>> = [
+            Some("2025-01-10T12:00:00Z"),
+            Some("2025-01-10T12:01:00Z"),
+            None,
+            Some("2025-01-10T12:03:14Z"),
+            Some("2025-01-10T12:00:00Z"),
+        ]
+        .into_iter()
+        .map(|ts| gaps.separator(&at(ts), &opts))
+        .collect();
+        assert_eq!(
+            lines,
+            [
+                None,
+                None,
+                None,
+                Some("  ⏱ 2m 14s gap".to_string()),
+                Some("  ⏱ 3m 14s gap".to_string()),
+            ]
+        );
+
+        let mut gaps = GapAnnotator::default();
+        assert_eq!(
+            gaps.separator(&at(Some("2025-01-10T12:00:00Z")), &opts),
+            None
+        );
+        assert_eq!(
+            gaps.separator(&at(Some("2025-01-11T12:00:00Z")), &opts),
+            None
+        );
+    }
+
+    // ── metadata_lines ──────────────────────────────────────────────
+
+    #[test]
+    fn metadata_lines_expands_one_level() {
+        let meta = json!({
+            "env": "staging",
+            "retries": 3,
+            "labels": { "team": "infra", "deep": { "x": 1 }, "ids": [1, 2] },
+            "tags": ["a", "b"],
+            "empty": {}
+        });
+        assert_eq!(
+            metadata_lines(&meta),
+            vec![
+                "empty: {0 keys}",
+                "env: staging",
+                "labels:",
+                "  deep: {1 keys}",
+                "  ids: [2 items]",
+                "  team: infra",
+                "retries: 3",
+                "tags: [2 items]",
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_lines_non_object() {
+        assert_eq!(metadata_lines(&json!([1, 2, 3])), vec!["[3 items]"]);
+        assert_eq!(metadata_lines(&json!("cli")), vec!["cli"]);
+    }
+
+    // ── format_tool_stats ───────────────────────────────────────────
+
+    #[test]
+    fn format_tool_stats_summary() {
+        let stats = ToolStats {
+            calls: 3,
+            errors: 1,
+            elapsed_seconds: 42,
+        };
+        assert_eq!(format_tool_stats(&stats), "3 calls, 1 errors, 42s");
+        let zero = ToolStats::default();
+        assert_eq!(format_tool_stats(&zero), "0 calls, 0 errors, 0s");
+    }
+
+    // ── compact_preview ─────────────────────────────────────────────
+
+    #[test]
+    fn compact_preview_flattens_newlines() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "user",
+            "message": { "content": "first line\n\n  second line" }
+        }))
+        .unwrap();
+        assert_eq!(
+            compact_preview(&event, &DisplayOptions::default()),
+            "first line second line"
+        );
+    }
+
+    #[test]
+    fn compact_preview_truncates() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "tool_use_summary",
+            "summary": "x".repeat(300)
+        }))
+        .unwrap();
+        let preview = compact_preview(&event, &DisplayOptions::default());
+        assert!(preview.ends_with("..."));
+        assert_eq!(preview.chars().count(), 103);
+    }
+
+    #[test]
+    fn compact_preview_assistant_blocks() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "assistant",
+            "message": { "content": [
+                { "type": "thinking", "thinking": "hidden" },
+                { "type": "text", "text": "Running tests" },
+                { "type": "tool_use", "name": "Bash", "input": {} }
+            ] }
+        }))
+        .unwrap();
+        assert_eq!(
+            compact_preview(&event, &DisplayOptions::default()),
+            "Running tests | tool_use: Bash"
+        );
+    }
+
+    #[test]
+    fn compact_preview_user_blocks() {
+        let with_text: SessionEvent = serde_json::from_value(json!({
+            "type": "user",
+            "message": { "content": [
+                { "type": "image", "source": {} },
+                { "type": "text", "text": "What is this?" }
+            ] }
+        }))
+        .unwrap();
+        assert_eq!(
+            compact_preview(&with_text, &DisplayOptions::default()),
+            "What is this?"
+        );
+
+        let no_text: SessionEvent = serde_json::from_value(json!({
+            "type": "user",
+            "message": { "content": [{ "type": "tool_result", "content": "ok" }] }
+        }))
+        .unwrap();
+        assert_eq!(
+            compact_preview(&no_text, &DisplayOptions::default()),
+            "(1 blocks)"
+        );
+    }
+
+    #[test]
+    fn print_user_blocks_doesnt_panic() {
+        let event: SessionEvent = serde_json::from_value(json!({
+            "type": "user",
+            "message": { "content": [
+                { "type": "text", "text": "hi" },
+                { "type": "image" },
+                { "type": "tool_result", "content": [{ "type": "text", "text": "out" }] },
+                { "no_type": true }
+            ] }
+        }))
+        .unwrap();
+        print_event(&event, &DisplayOptions::default());
+        print_event(
+            &event,
+            &DisplayOptions {
+                full_results: true,
+                ..Default::default()
+            },
+        );
+    }
+
     // ── print functions don't panic ─────────────────────────────────
 
     #[test]
@@ -707,14 +2148,15 @@ mod tests {
                         branches: Some(vec!["main".to_string()]),
                     }),
                 }]),
-                allowed_tools: None,
-                disallowed_tools: None,
+                allowed_tools: Some(vec!["Bash".to_string(), "Read".to_string()]),
+                disallowed_tools: Some(vec![]),
                 knowledge_base_ids: None,
             }),
-            metadata: None,
-            active_mount_paths: None,
+            metadata: Some(serde_json::json!({"source": "cli", "tags": ["a", "b"]})),
+            active_mount_paths: Some(vec!["/workspace".to_string()]),
         };
-        print_session_detail(&session);
+        print_session_detail(&session, false);
+        print_session_detail(&session, true);
     }
 
     #[test]
@@ -802,7 +2244,28 @@ mod tests {
         ];
 
         for event in &events {
-            print_event(event);
+            print_event(event, &DisplayOptions::default());
+            print_event(
+                event,
+                &DisplayOptions {
+                    wrap: 20,
+                    ..Default::default()
+                },
+            );
+            print_event(
+                event,
+                &DisplayOptions {
+                    compact: true,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn print_event_schema_all_dont_panic() {
+        for schema in EVENT_SCHEMAS {
+            print_event_schema(schema);
         }
     }
 
@@ -823,7 +2286,7 @@ mod tests {
             compact_metadata: None,
             extra: serde_json::Map::new(),
         };
-        print_logline(&log);
+        print_logline(&log, &DisplayOptions::default());
     }
 
     #[test]
@@ -843,6 +2306,35 @@ mod tests {
             compact_metadata: None,
             extra: serde_json::Map::new(),
         };
-        print_logline(&log);
+        print_logline(&log, &DisplayOptions::default());
+    }
+
+    #[test]
+    fn render_logline_truncates_unless_full() {
+        let content = format!("first line {}", "x".repeat(300));
+        let log: Logline = serde_json::from_value(json!({
+            "type": "system",
+            "content": content,
+        }))
+        .unwrap();
+        let plain = DisplayOptions {
+            plain: true,
+            ..Default::default()
+        };
+        let short = render_logline(&log, &plain);
+        assert_eq!(short.lines().count(), 2);
+        assert_eq!(short.lines().nth(1).unwrap().chars().count(), 202);
+
+        let full = DisplayOptions {
+            full_results: true,
+            wrap: 80,
+            ..plain
+        };
+        let long = render_logline(&log, &full);
+        let lines: Vec<_> = long.lines().skip(1).collect();
+        assert_eq!(lines[0], "  first line");
+        assert!(lines.iter().all(|l| l.chars().count() <= 80));
+        let shown: usize = lines.iter().map(|l| l.trim().len()).sum();
+        assert_eq!(shown, "first line".len() + 300);
     }
 }
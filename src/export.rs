@@ -0,0 +1,677 @@
+use anyhow::{Context, Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+
+use crate::display::{DisplayOptions, format_timestamp, render_event};
+use crate::types::*;
+
+// ── Saved exports ────────────────────────────────────────────────────
+
+/// Loads a JSON file previously written by the `export` command.
+pub fn load_export(path: &Path) -> Result<SessionExport> {
+    let json_str = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read export file {}", path.display()))?;
+    serde_json::from_str(&json_str)
+        .with_context(|| format!("Failed to parse export file {}", path.display()))
+}
+
+/// Loads events from a JSONL file, one event per line, as written by
+/// `export --format ndjson-events`. Blank lines are skipped.
+pub fn load_jsonl_events(path: &Path) -> Result<Vec<SessionEvent>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read JSONL file {}", path.display()))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Failed to parse line {} of {}", i + 1, path.display()))
+        })
+        .collect()
+}
+
+// ── JSON Pointer selection ───────────────────────────────────────────
+
+/// Checks RFC 6901 syntax: empty, or `/`-prefixed with `~` only in `~0`/`~1`.
+pub fn validate_pointer(pointer: &str) -> Result<()> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        bail!("Invalid JSON pointer '{pointer}': it must be empty or start with '/'");
+    }
+    let mut chars = pointer.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' && !matches!(chars.next(), Some('0' | '1')) {
+            bail!("Invalid JSON pointer '{pointer}': '~' must be followed by 0 or 1");
+        }
+    }
+    Ok(())
+}
+
+/// Resolves `pointer` against `value`, erroring if it is malformed or
+/// matches nothing.
+pub fn select_pointer<'a>(
+    value: &'a serde_json::Value,
+    pointer: &str,
+) -> Result<&'a serde_json::Value> {
+    validate_pointer(pointer)?;
+    value
+        .pointer(pointer)
+        .with_context(|| format!("JSON pointer '{pointer}' does not match anything in the export"))
+}
+
+// ── Content sizes ────────────────────────────────────────────────────
+
+/// Byte counts for the bulkiest kinds of event content.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ContentSizes {
+    /// UTF-8 bytes of assistant `text` blocks.
+    pub assistant_text: usize,
+    /// Serialized JSON bytes of every tool_result's content.
+    pub tool_output: usize,
+}
+
+pub fn content_sizes(events: &[SessionEvent]) -> ContentSizes {
+    let json_len = |v: &serde_json::Value| serde_json::to_string(v).map_or(0, |s| s.len());
+    let mut sizes = ContentSizes::default();
+    for event in events {
+        match event {
+            SessionEvent::Assistant(e) => {
+                for block in &e.message.content {
+                    match block {
+                        ContentBlock::Text(t) => {
+                            sizes.assistant_text += t.text.as_deref().map_or(0, str::len);
+                        }
+                        ContentBlock::ToolResult(r) => {
+                            sizes.tool_output += r.content.as_ref().map_or(0, json_len);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SessionEvent::User(e) => {
+                if let UserContent::Blocks(blocks) = &e.message.content {
+                    sizes.tool_output += blocks
+                        .iter()
+                        .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+                        .filter_map(|b| b.get("content"))
+                        .map(json_len)
+                        .sum::<usize>();
+                }
+            }
+            _ => {}
+        }
+    }
+    sizes
+}
+
+// ── Tool usage CSV ───────────────────────────────────────────────────
+
+/// One row per `ToolUseBlock`, correlated with its result and progress events.
+#[derive(Debug)]
+pub struct ToolUsageRow {
+    pub timestamp: String,
+    pub tool_name: String,
+    pub tool_use_id: String,
+    pub input_bytes: usize,
+    pub is_error: Option<bool>,
+    pub elapsed_seconds: Option<u64>,
+    /// Model from the most recent `system` event before the call.
+    pub model: Option<String>,
+}
+
+/// Returns `(tool_use_id, is_error)` for every tool_result block in the event.
+///
+/// Tool results show up as typed blocks in assistant messages and as raw
+/// JSON blocks in user messages, so both shapes are checked.
+pub fn tool_results(event: &SessionEvent) -> Vec<(&str, bool)> {
+    match event {
+        SessionEvent::Assistant(e) => e
+            .message
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolResult(r) => r
+                    .tool_use_id
+                    .as_deref()
+                    .map(|id| (id, r.is_error.unwrap_or(false))),
+                _ => None,
+            })
+            .collect(),
+        SessionEvent::User(e) => match &e.message.content {
+            UserContent::Blocks(blocks) => blocks
+                .iter()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+                .filter_map(|b| {
+                    let id = b.get("tool_use_id")?.as_str()?;
+                    let is_error = b.get("is_error").and_then(|v| v.as_bool());
+                    Some((id, is_error.unwrap_or(false)))
+                })
+                .collect(),
+            UserContent::Text(_) => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+pub fn tool_usage_rows(events: &[SessionEvent]) -> Vec<ToolUsageRow> {
+    let mut errors: HashMap<&str, bool> = HashMap::new();
+    let mut elapsed: HashMap<&str, u64> = HashMap::new();
+
+    for event in events {
+        for (id, is_error) in tool_results(event) {
+            *errors.entry(id).or_default() |= is_error;
+        }
+        if let SessionEvent::ToolProgress(p) = event
+            && let (Some(id), Some(secs)) = (p.tool_use_id.as_deref(), p.elapsed_time_seconds)
+        {
+            let max = elapsed.entry(id).or_default();
+            *max = (*max).max(secs);
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut model: Option<&str> = None;
+    for event in events {
+        if let SessionEvent::System(e) = event
+            && e.model.is_some()
+        {
+            model = e.model.as_deref();
+        }
+        let SessionEvent::Assistant(e) = event else {
+            continue;
+        };
+        for block in &e.message.content {
+            let ContentBlock::ToolUse(t) = block else {
+                continue;
+            };
+            let id = t.id.as_deref().unwrap_or("");
+            rows.push(ToolUsageRow {
+                timestamp: e.created_at.clone().unwrap_or_default(),
+                tool_name: t.name.clone().unwrap_or_default(),
+                tool_use_id: id.to_string(),
+                input_bytes: t
+                    .input
+                    .as_ref()
+                    .map(|v| serde_json::to_string(v).unwrap_or_default().len())
+                    .unwrap_or(0),
+                is_error: errors.get(id).copied(),
+                elapsed_seconds: elapsed.get(id).copied(),
+                model: model.map(str::to_string),
+            });
+        }
+    }
+    rows
+}
+
+/// Writes the tool usage table as CSV and returns the number of data rows.
+pub fn write_tool_usage_csv<W: Write>(events: &[SessionEvent], writer: W) -> Result<usize> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    wtr.write_record([
+        "timestamp",
+        "tool_name",
+        "tool_use_id",
+        "input_bytes",
+        "is_error",
+        "elapsed_seconds",
+    ])?;
+
+    let rows = tool_usage_rows(events);
+    for row in &rows {
+        wtr.write_record([
+            row.timestamp.as_str(),
+            row.tool_name.as_str(),
+            row.tool_use_id.as_str(),
+            &row.input_bytes.to_string(),
+            &row.is_error.map(|b| b.to_string()).unwrap_or_default(),
+            &row.elapsed_seconds
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(rows.len())
+}
+
+// ── Thinking removal ─────────────────────────────────────────────────
+
+/// Drops thinking blocks, and the `Other` blocks that carry redacted thinking
+/// and signatures, from an assistant message. Other events are left as is.
+pub fn strip_thinking(event: &mut SessionEvent) {
+    if let SessionEvent::Assistant(a) = event {
+        a.message
+            .content
+            .retain(|b| !matches!(b, ContentBlock::Thinking(_) | ContentBlock::Other));
+    }
+}
+
+// ── Tool result flattening ───────────────────────────────────────────
+
+fn block_type(block: &serde_json::Value) -> Option<&str> {
+    block.get("type")?.as_str()
+}
+
+/// Moves each tool_result block into the tool_use block it answers, as its
+/// `tool_result` field, in serialized `events`. Results whose tool_use isn't
+/// in `events` stay where they are. Events whose results all moved, typically
+/// user messages that only carried results, are kept with an empty `content`
+/// array so their metadata survives. Returns how many results were moved.
+pub fn flatten_tool_results(events: &mut [serde_json::Value]) -> usize {
+    let blocks_of = |event: &mut serde_json::Value| {
+        event
+            .pointer_mut("/message/content")
+            .and_then(|c| c.as_array_mut())
+            .map(std::mem::take)
+    };
+
+    let use_ids: HashSet<String> = events
+        .iter()
+        .filter_map(|e| e.pointer("/message/content")?.as_array())
+        .flatten()
+        .filter(|b| block_type(b) == Some("tool_use"))
+        .filter_map(|b| Some(b.get("id")?.as_str()?.to_string()))
+        .collect();
+
+    let mut results: HashMap<String, serde_json::Value> = HashMap::new();
+    for event in events.iter_mut() {
+        let Some(blocks) = blocks_of(event) else {
+            continue;
+        };
+        let mut kept = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let id = (block_type(&block) == Some("tool_result"))
+                .then(|| block.get("tool_use_id")?.as_str())
+                .flatten()
+                .filter(|id| use_ids.contains(*id) && !results.contains_key(*id))
+                .map(String::from);
+            match id {
+                Some(id) => {
+                    results.insert(id, block);
+                }
+                None => kept.push(block),
+            }
+        }
+        event["message"]["content"] = serde_json::Value::Array(kept);
+    }
+
+    let mut moved = 0;
+    for event in events.iter_mut() {
+        let Some(blocks) = event
+            .pointer_mut("/message/content")
+            .and_then(|c| c.as_array_mut())
+        else {
+            continue;
+        };
+        for block in blocks.iter_mut() {
+            let id = block.get("id").and_then(|id| id.as_str());
+            if block_type(block) == Some("tool_use")
+                && let Some(result) = id.and_then(|id| results.remove(id))
+            {
+                block["tool_result"] = result;
+                moved += 1;
+            }
+        }
+    }
+
+    moved
+}
+
+/// Writes `event` as a single line of JSON followed by a newline.
+pub fn write_ndjson_line<W: Write>(mut writer: W, event: &SessionEvent) -> Result<()> {
+    serde_json::to_writer(&mut writer, event)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Writes a plain-text transcript: a short session header followed by each
+/// event as `read` would show it, without colors.
+pub fn write_transcript_text<W: Write>(
+    session: &Session,
+    events: &[SessionEvent],
+    mut writer: W,
+) -> Result<()> {
+    writeln!(writer, "Session: {}", session.id)?;
+    if let Some(title) = &session.title {
+        writeln!(writer, "Title:   {title}")?;
+    }
+    if let Some(status) = &session.session_status {
+        writeln!(writer, "Status:  {status}")?;
+    }
+    if let Some(created) = &session.created_at {
+        writeln!(writer, "Created: {}", format_timestamp(created))?;
+    }
+    writeln!(writer, "Events:  {}", events.len())?;
+    writeln!(writer)?;
+    let opts = DisplayOptions {
+        plain: true,
+        ..Default::default()
+    };
+    for event in events {
+        writeln!(writer, "{}", render_event(event, &opts))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn events(json: serde_json::Value) -> Vec<SessionEvent> {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn load_export_roundtrip() {
+        let dir = std::env::temp_dir().join("cta-test-load-export");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("export.json");
+        let export = SessionExport {
+            session: serde_json::from_value(json!({ "id": "session_01roundtrip" })).unwrap(),
+            events: events(json!([{ "type": "user", "message": { "content": "hi" } }])),
+            exported_at: Some("2025-01-01T00:00:00+00:00".to_string()),
+            total_events: Some(1),
+        };
+        std::fs::write(&path, serde_json::to_string_pretty(&export).unwrap()).unwrap();
+
+        let loaded = load_export(&path).unwrap();
+        assert_eq!(loaded.session.id, "session_01roundtrip");
+        assert_eq!(loaded.events.len(), 1);
+        assert_eq!(loaded.events[0].event_type(), "user");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_jsonl_events_reads_ndjson_export_output() {
+        let dir = std::env::temp_dir().join("cta-test-load-jsonl");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("events.ndjson");
+        let mut buf = Vec::new();
+        for event in events(json!([
+            { "type": "user", "message": { "content": "hi" } },
+            { "type": "result", "duration_ms": 5 }
+        ])) {
+            write_ndjson_line(&mut buf, &event).unwrap();
+        }
+        buf.extend_from_slice(b"\n");
+        std::fs::write(&path, &buf).unwrap();
+
+        let loaded = load_jsonl_events(&path).unwrap();
+        let types: Vec<_> = loaded.iter().map(|e| e.event_type()).collect();
+        assert_eq!(types, ["user", "result"]);
+
+        std::fs::write(
+            &path,
+            "{\"type\":\"user\",\"message\":{\"content\":\"hi\"}}\nnot json\n",
+        )
+        .unwrap();
+        let err = load_jsonl_events(&path).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to parse line 2 of "));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_export_missing_file() {
+        let err = load_export(Path::new("/tmp/nonexistent-cta-export.json")).unwrap_err();
+        assert!(err.to_string().contains("Failed to read export file"));
+    }
+
+    #[test]
+    fn select_pointer_finds_subtree() {
+        let value = json!({ "events": [{ "message": { "content": "hi" } }], "a/b": { "~k": 1 } });
+        assert_eq!(
+            select_pointer(&value, "/events/0/message").unwrap(),
+            &json!({ "content": "hi" })
+        );
+        assert_eq!(select_pointer(&value, "/a~1b/~0k").unwrap(), &json!(1));
+        assert_eq!(select_pointer(&value, "").unwrap(), &value);
+    }
+
+    #[test]
+    fn select_pointer_errors_clearly() {
+        let value = json!({ "events": [] });
+        let missing = select_pointer(&value, "/events/0").unwrap_err();
+        assert!(missing.to_string().contains("does not match anything"));
+        let no_slash = select_pointer(&value, "events").unwrap_err();
+        assert!(no_slash.to_string().contains("start with '/'"));
+        let bad_escape = select_pointer(&value, "/a~2").unwrap_err();
+        assert!(bad_escape.to_string().contains("'~' must be followed"));
+    }
+
+    #[test]
+    fn content_sizes_counts_text_and_tool_output() {
+        let events = events(json!([
+            { "type": "assistant", "message": { "content": [
+                { "type": "text", "text": "héllo" },
+                { "type": "thinking", "thinking": "ignored" },
+                { "type": "tool_result", "tool_use_id": "tu_0", "content": "abc" }
+            ] } },
+            { "type": "user", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "tu_1", "content": [{ "type": "text", "text": "x" }] },
+                { "type": "text", "text": "not a tool result" }
+            ] } },
+            { "type": "user", "message": { "content": "plain text" } }
+        ]));
+        let sizes = content_sizes(&events);
+        assert_eq!(sizes.assistant_text, "héllo".len());
+        assert_eq!(
+            sizes.tool_output,
+            r#""abc""#.len() + r#"[{"text":"x","type":"text"}]"#.len()
+        );
+    }
+
+    #[test]
+    fn rows_correlate_results_and_progress() {
+        let events = events(json!([
+            {
+                "type": "assistant",
+                "created_at": "2025-01-01T00:00:00Z",
+                "message": { "content": [
+                    { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": { "command": "ls" } },
+                    { "type": "tool_use", "id": "tu_2", "name": "Read", "input": { "file_path": "/x" } }
+                ] }
+            },
+            { "type": "tool_progress", "tool_use_id": "tu_1", "elapsed_time_seconds": 2 },
+            { "type": "tool_progress", "tool_use_id": "tu_1", "elapsed_time_seconds": 7 },
+            {
+                "type": "user",
+                "message": { "content": [
+                    { "type": "tool_result", "tool_use_id": "tu_1", "content": "boom", "is_error": true },
+                    { "type": "tool_result", "tool_use_id": "tu_2", "content": "ok" }
+                ] }
+            }
+        ]));
+
+        let rows = tool_usage_rows(&events);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].tool_name, "Bash");
+        assert_eq!(rows[0].timestamp, "2025-01-01T00:00:00Z");
+        assert_eq!(rows[0].input_bytes, r#"{"command":"ls"}"#.len());
+        assert_eq!(rows[0].is_error, Some(true));
+        assert_eq!(rows[0].elapsed_seconds, Some(7));
+        assert_eq!(rows[1].is_error, Some(false));
+        assert_eq!(rows[1].elapsed_seconds, None);
+        assert!(rows[0].model.is_none());
+    }
+
+    #[test]
+    fn rows_take_model_from_latest_system_event() {
+        let events = events(json!([
+            { "type": "system", "subtype": "init", "model": "sonnet" },
+            { "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "id": "tu_1", "name": "Bash" }
+            ] } },
+            { "type": "system", "subtype": "init", "model": "opus" },
+            { "type": "system", "subtype": "status" },
+            { "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "id": "tu_2", "name": "Read" }
+            ] } }
+        ]));
+        let rows = tool_usage_rows(&events);
+        assert_eq!(rows[0].model.as_deref(), Some("sonnet"));
+        assert_eq!(rows[1].model.as_deref(), Some("opus"));
+    }
+
+    #[test]
+    fn rows_without_result_leave_error_empty() {
+        let events = events(json!([
+            { "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "id": "tu_9", "name": "Edit" }
+            ] } }
+        ]));
+        let rows = tool_usage_rows(&events);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].input_bytes, 0);
+        assert!(rows[0].is_error.is_none());
+    }
+
+    #[test]
+    fn csv_quotes_commas_and_newlines() {
+        let events = events(json!([
+            { "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "id": "tu_1", "name": "Weird, \"name\"\nhere" }
+            ] } }
+        ]));
+        let mut buf = Vec::new();
+        let count = write_tool_usage_csv(&events, &mut buf).unwrap();
+        assert_eq!(count, 1);
+
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.starts_with(
+                "timestamp,tool_name,tool_use_id,input_bytes,is_error,elapsed_seconds\n"
+            )
+        );
+        assert!(out.contains("\"Weird, \"\"name\"\"\nhere\""));
+
+        let mut rdr = csv::Reader::from_reader(out.as_bytes());
+        let record = rdr.records().next().unwrap().unwrap();
+        assert_eq!(&record[1], "Weird, \"name\"\nhere");
+    }
+
+    // ── strip_thinking ──────────────────────────────────────────────
+
+    #[test]
+    fn strip_thinking_keeps_text_and_tool_blocks() {
+        let mut events = events(json!([
+            { "type": "assistant", "message": { "content": [
+                { "type": "thinking", "thinking": "secret plan", "signature": "sig" },
+                { "type": "redacted_thinking", "data": "opaque" },
+                { "type": "text", "text": "Here you go" },
+                { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": {} }
+            ] } },
+            { "type": "user", "message": { "content": "thinking about it" } }
+        ]));
+        for event in &mut events {
+            strip_thinking(event);
+        }
+        let SessionEvent::Assistant(a) = &events[0] else {
+            panic!("expected assistant event");
+        };
+        assert_eq!(a.message.content.len(), 2);
+        assert!(matches!(a.message.content[0], ContentBlock::Text(_)));
+        assert!(matches!(a.message.content[1], ContentBlock::ToolUse(_)));
+
+        let json = serde_json::to_string(&events).unwrap();
+        assert!(!json.contains("secret plan"));
+        assert!(json.contains("thinking about it"));
+    }
+
+    // ── flatten_tool_results ────────────────────────────────────────
+
+    #[test]
+    fn flatten_nests_results_under_their_tool_use() {
+        let mut events = vec![
+            json!({ "type": "assistant", "message": { "content": [
+                { "type": "text", "text": "Checking" },
+                { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": {} },
+                { "type": "tool_use", "id": "tu_2", "name": "Read", "input": {} }
+            ] } }),
+            json!({ "type": "user", "uuid": "u_results", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "tu_1", "content": "ok" },
+                { "type": "tool_result", "tool_use_id": "tu_2", "content": "file", "is_error": false }
+            ] } }),
+            json!({ "type": "user", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "tu_gone", "content": "orphan" }
+            ] } }),
+            json!({ "type": "user", "message": { "content": "thanks" } }),
+        ];
+        assert_eq!(flatten_tool_results(&mut events), 2);
+
+        assert_eq!(events.len(), 4);
+        let uses = &events[0]["message"]["content"];
+        assert_eq!(uses[1]["tool_result"]["content"], "ok");
+        assert_eq!(uses[2]["tool_result"]["content"], "file");
+        assert_eq!(events[1]["uuid"], "u_results", "emptied events are kept");
+        assert_eq!(events[1]["message"]["content"], json!([]));
+        assert_eq!(
+            events[2]["message"]["content"][0]["tool_use_id"], "tu_gone",
+            "unmatched results stay at top level"
+        );
+        assert_eq!(events[3]["message"]["content"], "thanks");
+    }
+
+    #[test]
+    fn flatten_keeps_events_that_had_no_blocks() {
+        let mut events = vec![
+            json!({ "type": "assistant", "message": { "content": [] } }),
+            json!({ "type": "result", "duration_ms": 5 }),
+        ];
+        assert_eq!(flatten_tool_results(&mut events), 0);
+        assert_eq!(events.len(), 2);
+    }
+
+    // ── write_ndjson_line ───────────────────────────────────────────
+
+    #[test]
+    fn ndjson_lines_roundtrip() {
+        let events = events(json!([
+            { "type": "user", "message": { "content": "multi\nline" } },
+            { "type": "result", "duration_ms": 5 }
+        ]));
+        let mut buf = Vec::new();
+        for event in &events {
+            write_ndjson_line(&mut buf, event).unwrap();
+        }
+        let out = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: SessionEvent = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(parsed.event_type(), "user");
+        assert_eq!(
+            serde_json::from_str::<SessionEvent>(lines[1])
+                .unwrap()
+                .event_type(),
+            "result"
+        );
+    }
+
+    // ── write_transcript_text ───────────────────────────────────────
+
+    #[test]
+    fn transcript_text_has_header_and_plain_events() {
+        let session: Session = serde_json::from_value(json!({
+            "id": "session_01transcript",
+            "title": "Fix the build",
+            "session_status": "completed"
+        }))
+        .unwrap();
+        let events = events(json!([
+            { "type": "user", "message": { "content": "why does it fail?" } },
+            { "type": "assistant", "message": { "content": [
+                { "type": "text", "text": "Looking." },
+                { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": { "command": "ls" } }
+            ] } }
+        ]));
+        let mut buf = Vec::new();
+        write_transcript_text(&session, &events, &mut buf).unwrap();
+
+        let out = String::from_utf8(buf).unwrap();
+        assert!(!out.contains('\x1b'), "transcript contains ANSI escapes");
+        assert!(out.starts_with("Session: session_01transcript\nTitle:   Fix the build\n"));
+        assert!(out.contains(" USER\n  why does it fail?\n\n"));
+        assert!(out.contains(" ASSISTANT\n  Looking.\n  tool_use: Bash {\"command\":\"ls\"}\n"));
+    }
+}
@@ -0,0 +1,15 @@
+//! Read Claude Code remote sessions from the Anthropic API.
+//!
+//! The `claude-teleport-analyzer` binary is a thin CLI over this crate. Other
+//! programs can use [`client::ApiClient`] to fetch sessions and events, the
+//! serde types in [`types`], and the terminal formatting in [`display`].
+
+pub mod client;
+pub mod display;
+pub mod export;
+pub mod stats;
+pub mod theme;
+pub mod types;
+
+pub use client::{ApiClient, ApiError, ClientOptions, EventQuery, validate_session_id};
+pub use types::{Session, SessionEvent};
@@ -1,16 +1,27 @@
-mod client;
-mod display;
-mod types;
-
 use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Utc};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::{IsTerminal, Write};
+use std::ops::ControlFlow;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::{Notify, Semaphore};
+use tokio::task::JoinSet;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::prelude::*;
 
-use client::{ApiClient, validate_session_id};
-use display::*;
-use types::*;
+use claude_teleport_analyzer::client::{
+    self, ApiClient, ApiError, ClientOptions, EventQuery, Timeouts, load_credentials,
+    read_cached_summary, validate_session_id, verify_token, write_cached_summary,
+};
+use claude_teleport_analyzer::display::*;
+use claude_teleport_analyzer::export;
+use claude_teleport_analyzer::stats::{self, GroupBy};
+use claude_teleport_analyzer::theme::{self, ColorMode, Role, ThemeName, Themed};
+use claude_teleport_analyzer::types::*;
 
 // ── CLI ──────────────────────────────────────────────────────────────
 
@@ -20,13 +31,42 @@ use types::*;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Read session data from a saved `export` JSON file instead of the API
+    /// (supported by show, branches, read, event, summary, and stats)
+    #[arg(long, global = true)]
+    from_file: Option<String>,
+    /// Read events from a JSONL file (one event per line, as written by
+    /// `export -f ndjson-events`) instead of the API (same commands as --from-file)
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "from_file")]
+    from_jsonl: Option<String>,
+    /// Don't print fetch progress and status messages to stderr
+    #[arg(long, global = true)]
+    no_progress: bool,
+    /// Log API requests to stderr: -v for URLs, statuses, and pages, -vv for more
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Color theme for terminal output (defaults to $CTA_THEME, then dark)
+    #[arg(long, global = true, value_enum)]
+    theme: Option<ThemeName>,
+    /// When to use colors: auto (only if stdout is a terminal), always, or never
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    color: ColorMode,
+    /// Per-request timeout in seconds, 0 for none (defaults to $CTA_TIMEOUT, then 30)
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+    /// Connect timeout in seconds, 0 for none (defaults to $CTA_CONNECT_TIMEOUT, then 10)
+    #[arg(long, global = true, value_name = "SECS")]
+    connect_timeout: Option<u64>,
+    /// Events requested per page, 1-1000 (default: the API's page size)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=client::MAX_PAGE_SIZE as i64))]
+    page_size: Option<u32>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// List all remote sessions
     List {
-        /// Max number of sessions to show
+        /// Max number of sessions to show (0 = all)
         #[arg(short, long, default_value = "20")]
         limit: usize,
         /// Filter by status: running, idle, completed
@@ -35,423 +75,4124 @@ enum Commands {
         /// Only show sessions created after this date (YYYY-MM-DD or ISO8601)
         #[arg(long)]
         after: Option<String>,
+        /// Only show sessions created after this session was
+        #[arg(long, value_name = "SESSION_ID", conflicts_with = "after")]
+        since: Option<String>,
         /// Only show sessions created before this date (YYYY-MM-DD or ISO8601)
         #[arg(long)]
         before: Option<String>,
+        /// Only show sessions whose model contains this text (case-insensitive)
+        #[arg(long)]
+        model: Option<String>,
+        /// Only show sessions whose source URL or outcome repo contains this text
+        #[arg(long)]
+        repo: Option<String>,
+        /// Sort by field (dates newest first, text A-Z); sessions missing the field go last.
+        /// Default: most recently updated first
+        #[arg(long, value_enum)]
+        sort: Option<SortField>,
+        /// Reverse the sort order
+        #[arg(short, long, requires = "sort")]
+        reverse: bool,
+        /// Print sessions as a JSON array
+        #[arg(long)]
+        json: bool,
+        /// Redraw the list every N seconds, highlighting status changes (Ctrl-C to stop)
+        #[arg(long, value_name = "SECS", conflicts_with = "json", value_parser = clap::value_parser!(u64).range(1..))]
+        watch: Option<u64>,
+        /// Comma-separated session fields to keep in --json output (e.g. id,title,session_status)
+        #[arg(long, value_delimiter = ',', requires = "json")]
+        fields: Vec<String>,
+        /// Print --json output on a single line (default when stdout is not a terminal)
+        #[arg(long, requires = "json", conflicts_with = "pretty")]
+        compact: bool,
+        /// Pretty-print --json output even when stdout is not a terminal
+        #[arg(long, requires = "json")]
+        pretty: bool,
+        /// Print one aligned row per session with only these columns
+        /// (e.g. id,status,model,repo,updated)
+        #[arg(long, value_enum, value_delimiter = ',', conflicts_with_all = ["json", "watch"])]
+        columns: Vec<ListColumn>,
     },
     /// Show session metadata
     Show {
-        /// Session ID (e.g. session_01QJaJSUgfY6khmFTzJaMqph)
-        session_id: String,
+        /// Session ID (e.g. session_01QJaJSUgfY6khmFTzJaMqph); optional with --from-file
+        session_id: Option<String>,
+        /// Also show metadata, mount paths, tool permissions, and knowledge bases
+        #[arg(long)]
+        full: bool,
+        /// Refetch session metadata instead of using the local cache
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Show the repos and branches a session pushed to
+    Branches {
+        /// Session ID (optional with --from-file)
+        session_id: Option<String>,
+        /// Refetch session metadata instead of using the local cache
+        #[arg(long)]
+        refresh: bool,
     },
     /// Read the full conversation transcript of a session
     Read {
-        /// Session ID
-        session_id: String,
+        /// Session ID (optional with --from-file)
+        session_id: Option<String>,
         /// Only show user and assistant messages (skip tool_progress, env_manager_log, etc.)
         #[arg(short, long)]
         conversation_only: bool,
-        /// Filter by event type (user, assistant, system, tool_use_summary, etc.)
+        /// Only show the assistant's prose: assistant messages with text, minus
+        /// thinking and tool calls
+        #[arg(long)]
+        assistant_only: bool,
+        /// Filter by event type (user, assistant, system, tool_use_summary, etc.).
+        /// Repeat to keep several types.
         #[arg(short, long)]
-        r#type: Option<String>,
+        r#type: Vec<String>,
         /// Maximum number of events to fetch (0 = all)
         #[arg(short, long, default_value = "0")]
         max_events: usize,
+        /// Start fetching after the event with this UUID (the API's pagination cursor)
+        #[arg(long)]
+        after_id: Option<String>,
+        /// Stop fetching at the event with this UUID, excluding it
+        #[arg(long)]
+        before_id: Option<String>,
+        /// If a page of events fails mid-way, warn and show what was fetched
+        #[arg(long)]
+        best_effort: bool,
+        /// Only print the first N events that pass the other filters
+        #[arg(long, conflicts_with = "tail")]
+        head: Option<usize>,
+        /// Only print the last N events that pass the other filters
+        #[arg(long)]
+        tail: Option<usize>,
+        /// Print newest events first (applied after --head/--tail)
+        #[arg(short, long)]
+        reverse: bool,
         /// Search for text in event content (case-insensitive)
         #[arg(short, long)]
         search: Option<String>,
+        /// Only search these parts of events, comma-separated (default: all)
+        #[arg(long, requires = "search", value_enum, value_delimiter = ',')]
+        search_fields: Vec<SearchField>,
+        /// Only show calls to this tool (case-insensitive) and their results
+        #[arg(long, value_name = "NAME")]
+        filter_tool: Option<String>,
+        /// Drop env_manager_log entries below this severity (other events are unaffected)
+        #[arg(long, value_enum)]
+        min_level: Option<LogLevel>,
+        /// Keep only env_manager_log entries in this category, e.g. setup or network
+        /// (case-insensitive; other events are unaffected)
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
+        /// Wrap text content at this column (default: terminal width, 0 = no wrap)
+        #[arg(short, long)]
+        wrap: Option<usize>,
+        /// Skip user messages replayed from a previous run when the session was resumed
+        #[arg(long)]
+        exclude_replay: bool,
+        /// Show only failures: results with errors, error tool results, and error-level env logs
+        #[arg(long)]
+        only_errors: bool,
+        /// Print one line per event: timestamp, type, and a short preview
+        #[arg(long)]
+        compact: bool,
+        /// Print tool results in full instead of a 200-character preview
+        #[arg(long, conflicts_with = "compact")]
+        full_results: bool,
+        /// Prefix each event with the first 8 characters of its UUID
+        #[arg(long)]
+        show_uuid: bool,
+        /// Show event times as "2h ago" instead of absolute UTC timestamps
+        #[arg(long)]
+        relative_time: bool,
+        /// Collapse consecutive progress events for the same tool call into the last one
+        #[arg(long)]
+        dedup_progress: bool,
+        /// Pretty-print tool inputs as indented JSON, cutting only overlong values
+        #[arg(long)]
+        pretty_input: bool,
+        /// Preview length for thinking, tool input, and tool results (0 = no truncation)
+        #[arg(long, value_name = "N")]
+        truncate: Option<usize>,
+        /// Strip trailing whitespace and collapse 3+ blank lines into one in text and tool output
+        #[arg(long)]
+        trim: bool,
+        /// Print events as JSON lines, and each page's first_id/last_id/has_more to stderr
+        #[arg(long, conflicts_with_all = ["compact", "pretty_input"])]
+        raw: bool,
+        /// Print the selected events (after all filters) as one JSON array
+        #[arg(long, conflicts_with_all = ["raw", "compact", "pretty_input"])]
+        json_events: bool,
+        /// Print a "⏱ 2m 14s gap" line between events more than SECS apart
+        #[arg(long, value_name = "SECS", conflicts_with_all = ["raw", "json_events"], value_parser = clap::value_parser!(u64).range(1..))]
+        annotate_gaps: Option<u64>,
     },
     /// Show a compact summary of a session's conversation
     Summary {
+        /// Session ID (optional with --from-file)
+        session_id: Option<String>,
+        /// Refetch session metadata instead of using the local cache
+        #[arg(long)]
+        refresh: bool,
+        /// Show at most N tool use summaries and N user messages (0 = all)
+        #[arg(long, value_name = "N", default_value = "20")]
+        summary_limit: usize,
+        /// Print the summary as a JSON object instead of text
+        #[arg(long, conflicts_with = "summary_limit")]
+        json: bool,
+    },
+    /// Find one event by its UUID and print it in full
+    Event {
+        /// Session ID (optional with --from-file)
+        session_id: Option<String>,
+        /// The event's UUID
+        #[arg(long = "id", value_name = "EVENT_ID")]
+        event_id: String,
+        /// Print the event as pretty-printed JSON instead of rendering it
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Print (or run) the command that resumes a session locally
+    Resume {
         /// Session ID
         session_id: String,
+        /// Run `claude --teleport <id>` instead of printing it
+        #[arg(long)]
+        launch: bool,
+    },
+    /// Describe the fields of an event type (or list all known types)
+    Describe {
+        /// Event type, e.g. user, assistant, tool_progress
+        event_type: Option<String>,
+    },
+    /// Tool call counts and durations, optionally grouped
+    Stats {
+        /// Session ID (optional with --from-file)
+        session_id: Option<String>,
+        /// Bucket tool calls by time window, tool name, or model
+        #[arg(short, long, value_enum)]
+        group_by: Option<GroupBy>,
+        /// List the N slowest tool calls by tool_progress time (0 = don't)
+        #[arg(long, value_name = "N", default_value = "5")]
+        slowest: usize,
+        /// Show slow calls taking longer than SECS in red
+        #[arg(long, value_name = "SECS")]
+        slow_threshold: Option<u64>,
+    },
+    /// Search event content across multiple sessions
+    Search {
+        /// Text to search for (case-insensitive)
+        query: String,
+        /// Only search these parts of events, comma-separated (default: all)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        search_fields: Vec<SearchField>,
+        /// Number of sessions to search, in API order
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+        /// Only search sessions with this status: running, idle, completed
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Maximum number of events to fetch per session (0 = all)
+        #[arg(short, long, default_value = "0")]
+        max_events: usize,
+        /// Maximum number of sessions fetched concurrently
+        #[arg(short, long, default_value = "4", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+        /// Print only `session_id: N matches` lines, most matches first
+        #[arg(long)]
+        count_only: bool,
     },
     /// Show loglines from the session_ingress endpoint
     Loglines {
         /// Session ID
         session_id: String,
+        /// Drop sidechain loglines (e.g. subagent runs)
+        #[arg(long)]
+        main_only: bool,
+        /// Whether to include, exclude, or only show meta loglines
+        #[arg(long, value_enum, default_value = "include")]
+        meta: MetaMode,
+        /// Print full content, wrapped to the terminal, instead of a 200-char preview
+        #[arg(long)]
+        full: bool,
+        /// Only show loglines whose content contains this text (case-insensitive)
+        #[arg(long)]
+        grep: Option<String>,
+        /// Only show loglines with this type. Repeat to keep several types.
+        #[arg(short, long)]
+        r#type: Vec<String>,
+        /// Only show loglines at or after this time (YYYY-MM-DD or ISO8601)
+        #[arg(long)]
+        after: Option<String>,
+        /// Only show loglines at or before this time (YYYY-MM-DD or ISO8601)
+        #[arg(long)]
+        before: Option<String>,
     },
-    /// Export session events to a JSON file
+    /// Export session events to a JSON file, tool usage to CSV, or a plain-text transcript
     Export {
         /// Session ID
         session_id: String,
-        /// Output file path
-        #[arg(short, long, default_value = "session_export.json")]
-        output: String,
+        /// Output file path (default: session_export.json, session_tools.csv with --format csv,
+        /// session_transcript.txt with --format text, or session_events.ndjson)
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Output format: full JSON export, one CSV row per tool call, an uncolored transcript,
+        /// or one JSON event per line (streamed, for sessions too large to hold in memory)
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: ExportFormat,
+        /// Write only the subtree at this JSON Pointer, e.g. /events/0/message
+        #[arg(long)]
+        select: Option<String>,
+        /// Refetch session metadata instead of using the local cache
+        #[arg(long)]
+        refresh: bool,
+        /// If a page of events fails mid-way, warn and export what was fetched
+        #[arg(long)]
+        best_effort: bool,
+        /// Leave out the model's thinking (including redacted thinking) from assistant messages
+        #[arg(long)]
+        no_thinking: bool,
+        /// Nest each tool_result under the tool_use it answers (JSON exports only)
+        #[arg(long, alias = "flatten")]
+        flatten_tool_results: bool,
+    },
+    /// Show the account and organization the stored token belongs to
+    Whoami,
+    /// Check credentials and API connectivity
+    Doctor,
+}
+
+/// Severity of an env_manager_log entry, in ascending order.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Parses a level string from the API; unknown or missing levels count as info,
+    /// matching how they are displayed.
+    fn from_event(level: Option<&str>) -> Self {
+        match level.map(str::to_ascii_lowercase).as_deref() {
+            Some("debug" | "trace") => Self::Debug,
+            Some("warn" | "warning") => Self::Warn,
+            Some("error" | "fatal") => Self::Error,
+            _ => Self::Info,
+        }
+    }
+}
+
+/// How `loglines` treats entries flagged `isMeta`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum MetaMode {
+    #[default]
+    Include,
+    Exclude,
+    Only,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortField {
+    Created,
+    Updated,
+    Title,
+    Status,
+}
+
+/// A column of `list --columns` output.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ListColumn {
+    Id,
+    Title,
+    Status,
+    Model,
+    Repo,
+    Created,
+    Updated,
+}
+
+impl ListColumn {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Id => "ID",
+            Self::Title => "TITLE",
+            Self::Status => "STATUS",
+            Self::Model => "MODEL",
+            Self::Repo => "REPO",
+            Self::Created => "CREATED",
+            Self::Updated => "UPDATED",
+        }
+    }
+
+    /// The cell for `session`; missing values show as "-" so every row keeps
+    /// the same number of columns.
+    fn value(self, session: &Session) -> String {
+        let ctx = session.session_context.as_ref();
+        let cell = match self {
+            Self::Id => Some(session.id.clone()),
+            Self::Title => session.title.clone(),
+            Self::Status => session.session_status.clone(),
+            Self::Model => ctx.and_then(|c| c.model.clone()),
+            Self::Repo => session_repos(session).next().map(String::from),
+            Self::Created => session.created_at.as_deref().map(format_timestamp),
+            Self::Updated => session.updated_at.as_deref().map(format_timestamp),
+        };
+        cell.filter(|c| !c.is_empty())
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
+
+/// A header plus one line per session, each column padded to its widest
+/// cell. The last column isn't padded, so lines carry no trailing spaces.
+fn column_lines(sessions: &[&Session], columns: &[ListColumn]) -> Vec<String> {
+    let header = columns.iter().map(|c| c.header().to_string()).collect();
+    let rows: Vec<Vec<String>> = std::iter::once(header)
+        .chain(
+            sessions
+                .iter()
+                .map(|s| columns.iter().map(|c| c.value(s)).collect()),
+        )
+        .collect();
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let last = row.len().saturating_sub(1);
+            row.iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if i == last {
+                        cell.clone()
+                    } else {
+                        format!("{cell:<width$}", width = widths[i])
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+        })
+        .collect()
+}
+
+/// Whether `list --json` pretty-prints: explicit flags win, otherwise only
+/// when a person is likely reading (stdout is a terminal).
+fn pretty_json(pretty: bool, compact: bool, stdout_is_tty: bool) -> bool {
+    pretty || (!compact && stdout_is_tty)
+}
+
+enum ListOutput {
+    Table,
+    /// One aligned row per session with just these columns.
+    Columns(Vec<ListColumn>),
+    /// JSON array; an empty `fields` keeps every field.
+    Json {
+        fields: Vec<String>,
+        pretty: bool,
     },
+    /// Table redrawn every `interval`.
+    Watch {
+        interval: std::time::Duration,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Csv,
+    Text,
+    NdjsonEvents,
+}
+
+impl ExportFormat {
+    fn default_output(self) -> &'static str {
+        match self {
+            Self::Json => "session_export.json",
+            Self::Csv => "session_tools.csv",
+            Self::Text => "session_transcript.txt",
+            Self::NdjsonEvents => "session_events.ndjson",
+        }
+    }
+}
+
+// ── Data source ──────────────────────────────────────────────────────
+
+/// A local file that `show`, `branches`, `read`, `event`, `summary`, and
+/// `stats` can read instead of calling the API.
+enum InputFile {
+    /// A JSON document written by `export` (`--from-file`).
+    Export(String),
+    /// Bare events, one JSON object per line (`--from-jsonl`).
+    Jsonl(String),
+}
+
+impl InputFile {
+    fn from_flags(from_file: Option<String>, from_jsonl: Option<String>) -> Option<Self> {
+        from_file
+            .map(Self::Export)
+            .or_else(|| from_jsonl.map(Self::Jsonl))
+    }
+
+    fn flag(&self) -> &'static str {
+        match self {
+            Self::Export(_) => "--from-file",
+            Self::Jsonl(_) => "--from-jsonl",
+        }
+    }
+}
+
+/// Where session data comes from: the live API, a saved export file, or a
+/// JSONL file of events.
+enum Source {
+    Api(ApiClient),
+    File(Box<SessionExport>),
+}
+
+impl Source {
+    /// Validates `session_id` (if given) and opens the matching source.
+    ///
+    /// Returns the session ID to use; with an export file, it defaults to the
+    /// one stored in the export, and with a JSONL file to the file's path.
+    /// `client_opts` configures the API client.
+    async fn open(
+        session_id: Option<&str>,
+        from_file: Option<&InputFile>,
+        client_opts: ClientOptions,
+    ) -> Result<(Self, String)> {
+        if let Some(id) = session_id {
+            validate_session_id(id)?;
+        }
+        match from_file {
+            Some(InputFile::Jsonl(path)) => {
+                // No session metadata in a JSONL file; name it after the file
+                // unless an ID was given.
+                let events = export::load_jsonl_events(std::path::Path::new(path))?;
+                let id = session_id.map_or_else(|| path.clone(), str::to_string);
+                let data = SessionExport {
+                    session: Session {
+                        id: id.clone(),
+                        ..Default::default()
+                    },
+                    total_events: Some(events.len()),
+                    events,
+                    exported_at: None,
+                };
+                Ok((Self::File(Box::new(data)), id))
+            }
+            Some(InputFile::Export(path)) => {
+                let data = export::load_export(std::path::Path::new(path))?;
+                if let Some(id) = session_id
+                    && id != data.session.id
+                {
+                    bail!(
+                        "Export file {path} contains session {}, not {id}",
+                        data.session.id
+                    );
+                }
+                let id = data.session.id.clone();
+                Ok((Self::File(Box::new(data)), id))
+            }
+            None => {
+                let id = session_id
+                    .context(
+                        "A session ID is required unless --from-file or --from-jsonl is given",
+                    )?
+                    .to_string();
+                Ok((Self::Api(ApiClient::new(client_opts).await?), id))
+            }
+        }
+    }
+
+    /// `refresh` bypasses the on-disk cache of finished sessions.
+    async fn get_session(&self, session_id: &str, refresh: bool) -> Result<Session> {
+        match self {
+            Self::Api(api) => api.get_session_cached(session_id, refresh).await,
+            Self::File(data) => Ok(data.session.clone()),
+        }
+    }
+
+    async fn get_events(&self, session_id: &str, max_events: usize) -> Result<Vec<SessionEvent>> {
+        let query = EventQuery {
+            max_events,
+            ..Default::default()
+        };
+        self.get_events_query(session_id, &query).await
+    }
+
+    async fn get_events_query(
+        &self,
+        session_id: &str,
+        query: &EventQuery,
+    ) -> Result<Vec<SessionEvent>> {
+        match self {
+            Self::Api(api) => api.get_events_query(session_id, query).await,
+            Self::File(data) => Ok(query_events(&data.events, query)?.to_vec()),
+        }
+    }
+
+    /// The event whose UUID is `event_id`; the API source stops paging once
+    /// it turns up.
+    async fn find_event(&self, session_id: &str, event_id: &str) -> Result<Option<SessionEvent>> {
+        match self {
+            Self::Api(api) => api.find_event(session_id, event_id).await,
+            Self::File(data) => Ok(data
+                .events
+                .iter()
+                .find(|e| e.uuid() == Some(event_id))
+                .cloned()),
+        }
+    }
+
+    /// The session and the events selected by `query`, fetched concurrently
+    /// so that looking up the session doesn't add a round trip.
+    async fn session_and_events(
+        &self,
+        session_id: &str,
+        query: &EventQuery,
+    ) -> Result<(Session, Vec<SessionEvent>)> {
+        tokio::try_join!(
+            self.get_session(session_id, false),
+            self.get_events_query(session_id, query)
+        )
+    }
+
+    /// Passes each event selected by `query` to `callback` until it breaks;
+    /// the API source streams page by page. Returns how many events were passed.
+    async fn for_each_event<F>(
+        &self,
+        session_id: &str,
+        query: &EventQuery,
+        mut callback: F,
+    ) -> Result<usize>
+    where
+        F: FnMut(SessionEvent) -> Result<ControlFlow<()>>,
+    {
+        match self {
+            Self::Api(api) => api.for_each_event(session_id, query, callback).await,
+            Self::File(data) => {
+                let mut passed = 0;
+                for event in query_events(&data.events, query)? {
+                    passed += 1;
+                    if callback(event.clone())?.is_break() {
+                        break;
+                    }
+                }
+                Ok(passed)
+            }
+        }
+    }
+}
+
+/// Applies `query` to already-loaded events, the way the API would page
+/// through them. An `after_id` that isn't present is an error.
+fn query_events<'a>(events: &'a [SessionEvent], query: &EventQuery) -> Result<&'a [SessionEvent]> {
+    let position =
+        |events: &[SessionEvent], id: &str| events.iter().position(|e| e.uuid() == Some(id));
+    let mut events = events;
+    if let Some(ref after) = query.after_id {
+        let pos = position(events, after).with_context(|| format!("No event with ID {after}"))?;
+        events = &events[pos + 1..];
+    }
+    if let Some(ref before) = query.before_id
+        && let Some(pos) = position(events, before)
+    {
+        events = &events[..pos];
+    }
+    if query.max_events > 0 && events.len() > query.max_events {
+        events = &events[..query.max_events];
+    }
+    Ok(events)
+}
+
+/// Bails if `--from-file` or `--from-jsonl` was given to a command that
+/// always needs the API.
+fn require_api(command: &str, from_file: Option<&InputFile>) -> Result<()> {
+    if let Some(file) = from_file {
+        bail!("{} is not supported by the {command} command", file.flag());
+    }
+    Ok(())
 }
 
 // ── Helpers ──────────────────────────────────────────────────────────
 
-fn parse_date_filter(s: &str) -> Result<DateTime<Utc>> {
+/// Parses a `--after`/`--before` value. With `end_of_day`, a bare date means
+/// the last instant of that day so an upper bound includes the whole day.
+fn parse_date_filter(s: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
     // Try full ISO8601 first
     if let Ok(dt) = s.parse::<DateTime<Utc>>() {
         return Ok(dt);
     }
     // Try YYYY-MM-DD
     if let Ok(dt) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-        return Ok(dt.and_hms_opt(0, 0, 0).unwrap().and_utc());
+        let time = if end_of_day {
+            dt.and_hms_nano_opt(23, 59, 59, 999_999_999)
+        } else {
+            dt.and_hms_opt(0, 0, 0)
+        };
+        return Ok(time.unwrap().and_utc());
     }
     bail!("Invalid date format: '{s}'. Use YYYY-MM-DD or ISO8601 (e.g. 2025-01-15T00:00:00Z)")
 }
 
-fn event_contains_text(event: &SessionEvent, needle: &str) -> bool {
-    let needle_lower = needle.to_lowercase();
-    match event {
-        SessionEvent::User(e) => e
-            .message
-            .content
-            .as_text()
-            .is_some_and(|t| t.to_lowercase().contains(&needle_lower)),
-        SessionEvent::Assistant(e) => e.message.content.iter().any(|block| match block {
-            ContentBlock::Text(t) => t
-                .text
-                .as_deref()
-                .is_some_and(|s| s.to_lowercase().contains(&needle_lower)),
-            ContentBlock::Thinking(t) => t
-                .thinking
-                .as_deref()
-                .is_some_and(|s| s.to_lowercase().contains(&needle_lower)),
-            ContentBlock::ToolUse(t) => {
-                t.name
-                    .as_deref()
-                    .is_some_and(|s| s.to_lowercase().contains(&needle_lower))
-                    || t.input.as_ref().is_some_and(|v| {
-                        serde_json::to_string(v)
-                            .unwrap_or_default()
-                            .to_lowercase()
-                            .contains(&needle_lower)
-                    })
+/// Sorts sessions by `field`, keeping sessions without that field at the end
+/// regardless of `reverse`. Ties keep their API order.
+fn sort_sessions(sessions: &mut [&Session], field: SortField, reverse: bool) {
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    enum Key {
+        Time(DateTime<Utc>),
+        Text(String),
+    }
+
+    let key = |s: &Session| -> Option<Key> {
+        let ts = |t: &Option<String>| {
+            t.as_deref()
+                .and_then(|t| t.parse::<DateTime<Utc>>().ok())
+                .map(Key::Time)
+        };
+        let text = |t: &Option<String>| t.as_deref().map(|t| Key::Text(t.to_lowercase()));
+        match field {
+            SortField::Created => ts(&s.created_at),
+            SortField::Updated => ts(&s.updated_at),
+            SortField::Title => text(&s.title),
+            SortField::Status => text(&s.session_status),
+        }
+    };
+    // Dates sort newest first; text sorts A-Z.
+    let newest_first = matches!(field, SortField::Created | SortField::Updated);
+
+    sessions.sort_by(|a, b| match (key(a), key(b)) {
+        (Some(ka), Some(kb)) => {
+            let ord = ka.cmp(&kb);
+            if newest_first != reverse {
+                ord.reverse()
+            } else {
+                ord
             }
-            ContentBlock::ToolResult(t) => t.content.as_ref().is_some_and(|v| {
-                serde_json::to_string(v)
-                    .unwrap_or_default()
-                    .to_lowercase()
-                    .contains(&needle_lower)
-            }),
-            ContentBlock::Other => false,
-        }),
-        SessionEvent::ToolUseSummary(e) => e
-            .summary
+        }
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+/// Default `list` order: most recently updated first, ties broken by newest
+/// `created_at`. Sessions missing a timestamp go after those that have one.
+fn sort_by_activity(sessions: &mut [&Session]) {
+    let ts = |t: &Option<String>| t.as_deref().and_then(|t| t.parse::<DateTime<Utc>>().ok());
+    let newest_first = |a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>| match (a, b) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    };
+    sessions.sort_by(|a, b| {
+        newest_first(ts(&a.updated_at), ts(&b.updated_at))
+            .then_with(|| newest_first(ts(&a.created_at), ts(&b.created_at)))
+    });
+}
+
+fn validate_session_fields(fields: &[String]) -> Result<()> {
+    for field in fields {
+        if !SESSION_FIELDS.contains(&field.as_str()) {
+            bail!(
+                "Unknown session field '{field}'. Valid fields: {}",
+                SESSION_FIELDS.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Serializes a session, keeping only `fields` (all of them when empty).
+fn project_session(session: &Session, fields: &[String]) -> Result<serde_json::Value> {
+    let value = serde_json::to_value(session)?;
+    if fields.is_empty() {
+        return Ok(value);
+    }
+    let serde_json::Value::Object(mut map) = value else {
+        bail!("Session did not serialize to a JSON object");
+    };
+    let projected = fields
+        .iter()
+        .map(|f| (f.clone(), map.remove(f).unwrap_or(serde_json::Value::Null)))
+        .collect();
+    Ok(serde_json::Value::Object(projected))
+}
+
+/// Keeps just enough of a token to tell two tokens apart.
+fn redact_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() < 20 {
+        return "[REDACTED]".to_string();
+    }
+    let head: String = chars[..10].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}...{tail}")
+}
+
+/// Looks up an executable by name in the directories listed in `path_var`.
+fn find_in_path(name: &str, path_var: &std::ffi::OsStr) -> Option<std::path::PathBuf> {
+    let exts: &[&str] = if cfg!(windows) {
+        &["", ".exe", ".cmd", ".bat"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(path_var).find_map(|dir| {
+        exts.iter()
+            .map(|ext| dir.join(format!("{name}{ext}")))
+            .find(|p| p.is_file())
+    })
+}
+
+/// Event filters shared by `read` flags; an event must pass all of them.
+#[derive(Default)]
+struct EventFilter {
+    conversation_only: bool,
+    /// Keep only assistant messages that contain text.
+    assistant_only: bool,
+    types: Vec<String>,
+    search: Option<TextQuery>,
+    min_level: Option<LogLevel>,
+    /// Keep only env_manager_log entries in this category.
+    category: Option<String>,
+    /// Drop user turns replayed into the stream when a session is resumed.
+    exclude_replay: bool,
+    /// Keep only failure-related events; see `event_is_error`.
+    only_errors: bool,
+    /// Keep only calls to this tool and their results. Needs the whole event
+    /// list to pair results with calls, so it is applied by `filter_tool`
+    /// rather than `matches`.
+    tool: Option<String>,
+}
+
+impl EventFilter {
+    fn matches(&self, event: &SessionEvent) -> bool {
+        if self.exclude_replay
+            && let SessionEvent::User(u) = event
+            && u.is_replay == Some(true)
+        {
+            return false;
+        }
+        if !event_matches_types(event, &self.types) {
+            return false;
+        }
+        if self.conversation_only && !event.is_conversation() {
+            return false;
+        }
+        if self.assistant_only && !has_assistant_text(event) {
+            return false;
+        }
+        if !event_meets_level(event, self.min_level) {
+            return false;
+        }
+        if !event_in_category(event, self.category.as_deref()) {
+            return false;
+        }
+        if self.only_errors && !event_is_error(event) {
+            return false;
+        }
+        if let Some(ref query) = self.search
+            && !query.matches(event)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Session filters shared by `list` flags; a session must pass all of them.
+#[derive(Default)]
+struct SessionFilter {
+    status: Option<String>,
+    after: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+    /// Case-insensitive substring of `session_context.model`.
+    model: Option<String>,
+    /// Case-insensitive substring of any source URL or outcome repo name.
+    repo: Option<String>,
+}
+
+impl SessionFilter {
+    fn matches(&self, session: &Session) -> bool {
+        if let Some(ref f) = self.status
+            && session.session_status.as_deref() != Some(f.as_str())
+        {
+            return false;
+        }
+        let created_dt = session
+            .created_at
             .as_deref()
-            .is_some_and(|s| s.to_lowercase().contains(&needle_lower)),
-        SessionEvent::EnvManagerLog(e) => e
-            .data
-            .as_ref()
-            .and_then(|d| d.content.as_deref())
-            .is_some_and(|s| s.to_lowercase().contains(&needle_lower)),
-        SessionEvent::System(e) => e
-            .subtype
+            .and_then(|c| c.parse::<DateTime<Utc>>().ok());
+        if let (Some(after), Some(dt)) = (&self.after, &created_dt)
+            && dt < after
+        {
+            return false;
+        }
+        if let (Some(before), Some(dt)) = (&self.before, &created_dt)
+            && dt > before
+        {
+            return false;
+        }
+        if let Some(ref needle) = self.model {
+            let model = session
+                .session_context
+                .as_ref()
+                .and_then(|c| c.model.as_deref());
+            if !model.is_some_and(|m| m.to_lowercase().contains(&needle.to_lowercase())) {
+                return false;
+            }
+        }
+        if let Some(ref needle) = self.repo
+            && !session_repos(session).any(|r| r.to_lowercase().contains(&needle.to_lowercase()))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Source URLs and outcome repo names a session is associated with.
+fn session_repos(session: &Session) -> impl Iterator<Item = &str> {
+    let ctx = session.session_context.as_ref();
+    let urls = ctx
+        .and_then(|c| c.sources.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|s| s.url.as_deref());
+    let repos = ctx
+        .and_then(|c| c.outcomes.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|o| o.git_info.as_ref()?.repo.as_deref());
+    urls.chain(repos)
+}
+
+fn logline_passes(log: &Logline, main_only: bool, meta: MetaMode) -> bool {
+    if main_only && log.is_sidechain == Some(true) {
+        return false;
+    }
+    let is_meta = log.is_meta == Some(true);
+    match meta {
+        MetaMode::Include => true,
+        MetaMode::Exclude => !is_meta,
+        MetaMode::Only => is_meta,
+    }
+}
+
+/// Which loglines `loglines` shows.
+#[derive(Default)]
+struct LoglineFilter {
+    main_only: bool,
+    meta: MetaMode,
+    /// Lowercased `--grep` text.
+    needle: Option<String>,
+    /// Logline types to keep; empty keeps all.
+    types: Vec<String>,
+    after: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+}
+
+impl LoglineFilter {
+    /// Loglines without a parseable timestamp pass `after`/`before`, as
+    /// sessions without `created_at` do in `list`.
+    fn matches(&self, log: &Logline) -> bool {
+        if !logline_passes(log, self.main_only, self.meta) {
+            return false;
+        }
+        if let Some(ref needle) = self.needle
+            && !logline_matches(log, needle)
+        {
+            return false;
+        }
+        if !self.types.is_empty()
+            && !log
+                .log_type
+                .as_ref()
+                .is_some_and(|t| self.types.contains(t))
+        {
+            return false;
+        }
+        let at = log
+            .timestamp
             .as_deref()
-            .is_some_and(|s| s.to_lowercase().contains(&needle_lower)),
-        _ => false,
+            .and_then(|t| t.parse::<DateTime<Utc>>().ok());
+        if let (Some(after), Some(at)) = (self.after, at)
+            && at < after
+        {
+            return false;
+        }
+        if let (Some(before), Some(at)) = (self.before, at)
+            && at > before
+        {
+            return false;
+        }
+        true
     }
 }
 
-// ── Commands ─────────────────────────────────────────────────────────
+/// True if the logline's content contains `needle_lower` (already lowercased).
+fn logline_matches(log: &Logline, needle_lower: &str) -> bool {
+    log.content
+        .as_deref()
+        .is_some_and(|c| c.to_lowercase().contains(needle_lower))
+}
 
-async fn cmd_list(
-    limit: usize,
-    status_filter: Option<String>,
-    after: Option<String>,
-    before: Option<String>,
-) -> Result<()> {
-    let after_dt = after.as_deref().map(parse_date_filter).transpose()?;
-    let before_dt = before.as_deref().map(parse_date_filter).transpose()?;
+/// Which slice of the filtered events `read` prints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Window {
+    All,
+    Head(usize),
+    Tail(usize),
+}
 
-    let api = ApiClient::new().await?;
-    let sessions = api.list_sessions().await?;
+impl Window {
+    fn new(head: Option<usize>, tail: Option<usize>) -> Self {
+        match (head, tail) {
+            (Some(n), _) => Self::Head(n),
+            (None, Some(n)) => Self::Tail(n),
+            (None, None) => Self::All,
+        }
+    }
 
-    let filtered: Vec<&Session> = sessions
-        .iter()
-        .filter(|s| {
-            if let Some(ref f) = status_filter
-                && s.session_status.as_deref() != Some(f.as_str())
-            {
-                return false;
+    fn apply<T>(self, items: &[T]) -> &[T] {
+        match self {
+            Self::All => items,
+            Self::Head(n) => &items[..n.min(items.len())],
+            Self::Tail(n) => &items[items.len().saturating_sub(n)..],
+        }
+    }
+}
+
+/// The window of filtered events `read` prints, and their order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Selection {
+    window: Window,
+    /// Newest first; applied after the window, so `--tail 5` shows the
+    /// last five events with the latest on top.
+    reverse: bool,
+    /// Collapse runs of tool_progress events before windowing; see
+    /// `dedup_progress`.
+    dedup_progress: bool,
+    output: ReadOutput,
+}
+
+/// How `read` prints the events it selected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReadOutput {
+    /// Each event in turn, with a separator between printed events more than
+    /// `annotate_gaps` seconds apart when set.
+    Transcript { annotate_gaps: Option<u64> },
+    /// All selected events as one pretty-printed JSON array.
+    JsonEvents,
+}
+
+impl Selection {
+    /// Whether events can be printed as they arrive: only `--head`-free,
+    /// `--tail`-free, forward transcripts without progress dedup qualify.
+    fn is_streamable(self) -> bool {
+        matches!(self.window, Window::All)
+            && !self.reverse
+            && !self.dedup_progress
+            && self.output != ReadOutput::JsonEvents
+    }
+
+    /// Separators for `--annotate-gaps`, off unless the transcript asks.
+    fn gap_annotator(self) -> GapAnnotator {
+        match self.output {
+            ReadOutput::Transcript { annotate_gaps } => GapAnnotator::new(annotate_gaps),
+            ReadOutput::JsonEvents => GapAnnotator::default(),
+        }
+    }
+
+    fn apply<T>(self, items: &[T]) -> Vec<&T> {
+        let windowed = self.window.apply(items).iter();
+        if self.reverse {
+            windowed.rev().collect()
+        } else {
+            windowed.collect()
+        }
+    }
+}
+
+/// Collapses each run of consecutive tool_progress events for the same
+/// `tool_use_id` into its last event, which carries the final elapsed time.
+fn dedup_progress(events: Vec<&SessionEvent>) -> Vec<&SessionEvent> {
+    let progress_id = |event: &SessionEvent| match event {
+        SessionEvent::ToolProgress(p) => p.tool_use_id.clone(),
+        _ => None,
+    };
+    let mut kept: Vec<&SessionEvent> = Vec::with_capacity(events.len());
+    for event in events {
+        if let Some(id) = progress_id(event)
+            && kept.last().and_then(|prev| progress_id(prev)) == Some(id)
+        {
+            kept.pop();
+        }
+        kept.push(event);
+    }
+    kept
+}
+
+/// Keeps assistant events that call the tool `name` (case-insensitive), and
+/// the events carrying those calls' results.
+fn filter_tool<'a>(events: Vec<&'a SessionEvent>, name: &str) -> Vec<&'a SessionEvent> {
+    let mut call_ids: HashSet<&str> = HashSet::new();
+    events
+        .into_iter()
+        .filter(|&event| {
+            let mut calls_tool = false;
+            if let SessionEvent::Assistant(e) = event {
+                for block in &e.message.content {
+                    if let ContentBlock::ToolUse(t) = block
+                        && t.name
+                            .as_deref()
+                            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                    {
+                        calls_tool = true;
+                        call_ids.extend(t.id.as_deref());
+                    }
+                }
             }
-            let created_dt = s
-                .created_at
-                .as_deref()
-                .and_then(|c| c.parse::<DateTime<Utc>>().ok());
-            if let (Some(after), Some(dt)) = (&after_dt, &created_dt)
-                && dt < after
-            {
-                return false;
+            calls_tool
+                || export::tool_results(event)
+                    .iter()
+                    .any(|(id, _)| call_ids.contains(id))
+        })
+        .collect()
+}
+
+/// Returns false only for env_manager_log events below `min_level`.
+fn event_meets_level(event: &SessionEvent, min_level: Option<LogLevel>) -> bool {
+    match (event, min_level) {
+        (SessionEvent::EnvManagerLog(e), Some(min)) => {
+            LogLevel::from_event(e.data.as_ref().and_then(|d| d.level.as_deref())) >= min
+        }
+        _ => true,
+    }
+}
+
+/// Returns false only for env_manager_log events outside `category`.
+fn event_in_category(event: &SessionEvent, category: Option<&str>) -> bool {
+    match (event, category) {
+        (SessionEvent::EnvManagerLog(e), Some(wanted)) => e
+            .data
+            .as_ref()
+            .and_then(|d| d.category.as_deref())
+            .is_some_and(|c| c.eq_ignore_ascii_case(wanted)),
+        _ => true,
+    }
+}
+
+/// True for assistant messages with at least one non-empty text block.
+fn has_assistant_text(event: &SessionEvent) -> bool {
+    let SessionEvent::Assistant(a) = event else {
+        return false;
+    };
+    a.message.content.iter().any(|block| match block {
+        ContentBlock::Text(t) => t.text.as_deref().is_some_and(|s| !s.trim().is_empty()),
+        _ => false,
+    })
+}
+
+/// Returns true for results that report errors, messages carrying a tool
+/// result with `is_error: true`, and env_manager_log entries at error level.
+fn event_is_error(event: &SessionEvent) -> bool {
+    match event {
+        SessionEvent::Result(e) => e.errors.as_ref().is_some_and(|errs| !errs.is_empty()),
+        SessionEvent::Assistant(e) => {
+            e.message.content.iter().any(
+                |block| matches!(block, ContentBlock::ToolResult(r) if r.is_error == Some(true)),
+            )
+        }
+        SessionEvent::User(e) => match &e.message.content {
+            UserContent::Blocks(blocks) => blocks.iter().any(|block| {
+                block.get("type").and_then(|t| t.as_str()) == Some("tool_result")
+                    && block.get("is_error").and_then(|v| v.as_bool()) == Some(true)
+            }),
+            UserContent::Text(_) => false,
+        },
+        SessionEvent::EnvManagerLog(e) => {
+            LogLevel::from_event(e.data.as_ref().and_then(|d| d.level.as_deref()))
+                == LogLevel::Error
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if `types` is empty or the event's type matches any entry.
+fn event_matches_types(event: &SessionEvent, types: &[String]) -> bool {
+    types.is_empty() || types.iter().any(|t| event.event_type() == t)
+}
+
+/// Part of an event that `--search-fields` can restrict text search to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum SearchField {
+    /// Text of user messages
+    User,
+    /// Text blocks of assistant messages
+    AssistantText,
+    /// Assistant thinking blocks
+    Thinking,
+    /// Names of tools called
+    ToolName,
+    /// Tool call inputs, as JSON
+    ToolInput,
+    /// Tool result content, as JSON
+    ToolResult,
+}
+
+/// Text to look for in events, and which parts of each event to look in.
+#[derive(Clone, Debug)]
+struct TextQuery {
+    text: String,
+    /// Empty means every part, including summaries, env logs, and system subtypes.
+    fields: Vec<SearchField>,
+}
+
+impl TextQuery {
+    fn matches(&self, event: &SessionEvent) -> bool {
+        event_contains_text(event, &self.text, &self.fields)
+    }
+}
+
+/// Case-insensitive search of `event` for `needle`, looking only at the parts
+/// named in `fields` (all of them if it is empty).
+fn event_contains_text(event: &SessionEvent, needle: &str, fields: &[SearchField]) -> bool {
+    let needle_lower = needle.to_lowercase();
+    let all = fields.is_empty();
+    let wants = |field| all || fields.contains(&field);
+    let in_str = |s: Option<&str>| s.is_some_and(|s| s.to_lowercase().contains(&needle_lower));
+    let in_json = |v: Option<&serde_json::Value>| {
+        v.is_some_and(|v| {
+            serde_json::to_string(v)
+                .unwrap_or_default()
+                .to_lowercase()
+                .contains(&needle_lower)
+        })
+    };
+    match event {
+        SessionEvent::User(e) => {
+            let in_text = wants(SearchField::User)
+                && e.message
+                    .content
+                    .texts()
+                    .into_iter()
+                    .any(|t| in_str(Some(t)));
+            let in_tool_result = wants(SearchField::ToolResult)
+                && match &e.message.content {
+                    UserContent::Blocks(blocks) => blocks
+                        .iter()
+                        .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+                        .any(|b| in_json(b.get("content"))),
+                    UserContent::Text(_) => false,
+                };
+            in_text || in_tool_result
+        }
+        SessionEvent::Assistant(e) => e.message.content.iter().any(|block| match block {
+            ContentBlock::Text(t) => wants(SearchField::AssistantText) && in_str(t.text.as_deref()),
+            ContentBlock::Thinking(t) => {
+                wants(SearchField::Thinking) && in_str(t.thinking.as_deref())
             }
-            if let (Some(before), Some(dt)) = (&before_dt, &created_dt)
-                && dt > before
-            {
-                return false;
+            ContentBlock::ToolUse(t) => {
+                (wants(SearchField::ToolName) && in_str(t.name.as_deref()))
+                    || (wants(SearchField::ToolInput) && in_json(t.input.as_ref()))
             }
-            true
+            ContentBlock::ToolResult(t) => {
+                wants(SearchField::ToolResult) && in_json(t.content.as_ref())
+            }
+            ContentBlock::Other => false,
+        }),
+        SessionEvent::ToolUseSummary(e) => all && in_str(e.summary.as_deref()),
+        SessionEvent::EnvManagerLog(e) => {
+            all && in_str(e.data.as_ref().and_then(|d| d.content.as_deref()))
+        }
+        SessionEvent::System(e) => all && in_str(e.subtype.as_deref()),
+        _ => false,
+    }
+}
+
+/// The `init` system event, or the first system event if none is tagged.
+fn init_system_event(events: &[SessionEvent]) -> Option<&SystemEvent> {
+    let mut systems = events.iter().filter_map(|e| match e {
+        SessionEvent::System(s) => Some(s),
+        _ => None,
+    });
+    let first = systems.next()?;
+    if first.subtype.as_deref() == Some("init") {
+        return Some(first);
+    }
+    systems
+        .find(|s| s.subtype.as_deref() == Some("init"))
+        .or(Some(first))
+}
+
+/// Warns on stderr when `session` may still receive events, since whatever
+/// is printed or exported from it is then only a partial transcript.
+fn warn_if_live(session: &Session) {
+    if session.is_live() {
+        eprintln!(
+            "{} session is still {}; transcript may be incomplete",
+            "warning:".themed(Role::Warning),
+            session.session_status.as_deref().unwrap_or_default()
+        );
+    }
+}
+
+/// What `summary` prints below the session title, derived from its events.
+/// Serializable so `summary` can cache it; see `read_cached_summary`.
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SessionSummary {
+    total_events: usize,
+    /// Event types with their counts, most frequent first.
+    type_counts: Vec<(String, usize)>,
+    /// Tools, agents, and skills from the init event, as (label, names)
+    /// pairs; labels with no names are left out.
+    capabilities: Vec<(String, Vec<String>)>,
+    /// MCP servers from the init event, one description each.
+    mcp_servers: Vec<String>,
+    timing: stats::SessionTiming,
+    tool_summaries: Vec<String>,
+    /// Text user messages, cut to 120 chars.
+    user_messages: Vec<String>,
+}
+
+/// The `summary --json` object: the headline numbers a dashboard needs,
+/// without the init-event details.
+#[derive(Debug, serde::Serialize)]
+struct SummaryJson<'a> {
+    title: Option<&'a str>,
+    status: Option<&'a str>,
+    total_events: usize,
+    /// Event counts keyed by type.
+    type_counts: std::collections::BTreeMap<&'a str, usize>,
+    tool_summaries: &'a [String],
+    user_message_count: usize,
+    wall_clock_ms: Option<u64>,
+}
+
+impl<'a> SummaryJson<'a> {
+    fn new(session: &'a Session, summary: &'a SessionSummary) -> Self {
+        Self {
+            title: session.title.as_deref(),
+            status: session.session_status.as_deref(),
+            total_events: summary.total_events,
+            type_counts: summary
+                .type_counts
+                .iter()
+                .map(|(t, c)| (t.as_str(), *c))
+                .collect(),
+            tool_summaries: &summary.tool_summaries,
+            user_message_count: summary.user_messages.len(),
+            wall_clock_ms: summary.timing.wall_clock_ms,
+        }
+    }
+}
+
+fn summarize_events(events: &[SessionEvent]) -> SessionSummary {
+    let mut type_counts: HashMap<&str, usize> = HashMap::new();
+    for e in events {
+        *type_counts.entry(e.event_type()).or_default() += 1;
+    }
+    let mut type_counts: Vec<(String, usize)> = type_counts
+        .into_iter()
+        .map(|(t, c)| (t.to_string(), c))
+        .collect();
+    type_counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let capabilities = init_system_event(events)
+        .map(|init| {
+            let owned = |names: Option<&Vec<String>>| names.cloned().unwrap_or_default();
+            [
+                ("Tools", owned(init.tools.as_ref())),
+                ("Agents", owned(init.agents.as_ref())),
+                ("Skills", owned(init.skills.as_ref())),
+            ]
+            .into_iter()
+            .filter(|(_, names)| !names.is_empty())
+            .map(|(label, names)| (label.to_string(), names))
+            .collect()
         })
-        .take(limit)
+        .unwrap_or_default();
+    let mcp_servers = init_system_event(events)
+        .map(SystemEvent::mcp_server_descriptions)
+        .unwrap_or_default();
+
+    let tool_summaries = events
+        .iter()
+        .filter_map(|e| match e {
+            SessionEvent::ToolUseSummary(s) => s.summary.clone(),
+            _ => None,
+        })
+        .collect();
+
+    let user_messages = events
+        .iter()
+        .filter_map(|e| match e {
+            SessionEvent::User(u) => u.message.content.as_text(),
+            _ => None,
+        })
+        .map(|msg| {
+            let preview: String = msg.chars().take(120).collect();
+            let suffix = if msg.len() > 120 { "..." } else { "" };
+            format!("{preview}{suffix}")
+        })
+        .collect();
+
+    SessionSummary {
+        total_events: events.len(),
+        type_counts,
+        capabilities,
+        mcp_servers,
+        timing: stats::session_timing(events),
+        tool_summaries,
+        user_messages,
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────────────────
+
+/// The `--after` bound for `list --since`: just past the reference session's
+/// creation, so the reference itself is left out.
+fn since_bound(reference: &Session) -> Result<DateTime<Utc>> {
+    let created = reference
+        .created_at
+        .as_deref()
+        .with_context(|| format!("Session {} has no created_at to list from", reference.id))?;
+    let created: DateTime<Utc> = created.parse().with_context(|| {
+        format!(
+            "Session {} has an unreadable created_at '{created}'",
+            reference.id
+        )
+    })?;
+    Ok(created + chrono::Duration::nanoseconds(1))
+}
+
+async fn cmd_list(
+    limit: usize,
+    mut filter: SessionFilter,
+    since: Option<&str>,
+    sort: Option<SortField>,
+    reverse: bool,
+    output: ListOutput,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    if let ListOutput::Json { fields, .. } = &output {
+        validate_session_fields(fields)?;
+    }
+    if let Some(reference) = since {
+        validate_session_id(reference)?;
+    }
+
+    let api = ApiClient::new(client_opts).await?;
+    if let Some(reference) = since {
+        filter.after = Some(since_bound(&api.get_session(reference).await?)?);
+    }
+    if let ListOutput::Watch { interval } = output {
+        return watch_sessions(&api, limit, &filter, sort, reverse, interval).await;
+    }
+    let sessions = api.list_sessions().await?;
+    let filtered = select_sessions(&sessions, limit, &filter, sort, reverse);
+
+    if let ListOutput::Json { fields, pretty } = &output {
+        let projected = filtered
+            .iter()
+            .map(|s| project_session(s, fields))
+            .collect::<Result<Vec<_>>>()?;
+        let json = if *pretty {
+            serde_json::to_string_pretty(&projected)?
+        } else {
+            serde_json::to_string(&projected)?
+        };
+        println!("{json}");
+        return Ok(());
+    }
+    if let ListOutput::Columns(columns) = &output {
+        for line in column_lines(&filtered, columns) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
+    println!(
+        "\n{} ({} total, showing {})\n",
+        "Remote Sessions".bold(),
+        sessions.len(),
+        filtered.len()
+    );
+
+    for s in &filtered {
+        print_session_row(s);
+    }
+
+    Ok(())
+}
+
+/// Applies `list`'s filter, sort, and limit (0 = no limit).
+fn select_sessions<'a>(
+    sessions: &'a [Session],
+    limit: usize,
+    filter: &SessionFilter,
+    sort: Option<SortField>,
+    reverse: bool,
+) -> Vec<&'a Session> {
+    let mut filtered: Vec<&Session> = sessions.iter().filter(|s| filter.matches(s)).collect();
+    match sort {
+        Some(field) => sort_sessions(&mut filtered, field, reverse),
+        None => sort_by_activity(&mut filtered),
+    }
+    if limit > 0 {
+        filtered.truncate(limit);
+    }
+    filtered
+}
+
+/// Returns the previous status if `session` was seen last poll with a
+/// different one. Sessions new since the last poll don't count as changed.
+fn status_change<'a>(
+    previous: &'a HashMap<String, Option<String>>,
+    session: &Session,
+) -> Option<Option<&'a str>> {
+    let before = previous.get(&session.id)?;
+    (before != &session.session_status).then_some(before.as_deref())
+}
+
+/// Redraws the session list every `interval` until Ctrl-C.
+async fn watch_sessions(
+    api: &ApiClient,
+    limit: usize,
+    filter: &SessionFilter,
+    sort: Option<SortField>,
+    reverse: bool,
+    interval: std::time::Duration,
+) -> Result<()> {
+    WATCHING.store(true, Ordering::SeqCst);
+    let mut previous: HashMap<String, Option<String>> = HashMap::new();
+    loop {
+        let sessions = api.list_sessions().await?;
+        let filtered = select_sessions(&sessions, limit, filter, sort, reverse);
+
+        // Clear the screen and move the cursor home.
+        print!("\x1B[2J\x1B[H");
+        println!(
+            "\n{} ({} total, showing {}) - every {}s, updated {}, Ctrl-C to stop\n",
+            "Remote Sessions".bold(),
+            sessions.len(),
+            filtered.len(),
+            interval.as_secs(),
+            Utc::now().format("%H:%M:%S UTC")
+        );
+        for s in &filtered {
+            print_session_row(s);
+            if let Some(before) = status_change(&previous, s) {
+                println!(
+                    "    {} {} -> {}\n",
+                    "status changed:".themed(Role::Warning).bold(),
+                    before.unwrap_or("unknown"),
+                    status_colored(s.session_status.as_deref().unwrap_or("unknown"))
+                );
+            }
+        }
+
+        previous = sessions
+            .iter()
+            .map(|s| (s.id.clone(), s.session_status.clone()))
+            .collect();
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = WATCH_INTERRUPT.notified() => {
+                println!();
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn cmd_show(
+    session_id: Option<&str>,
+    from_file: Option<&InputFile>,
+    full: bool,
+    refresh: bool,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    let (source, session_id) = Source::open(session_id, from_file, client_opts).await?;
+    let session = source.get_session(&session_id, refresh).await?;
+    print_session_detail(&session, full);
+    Ok(())
+}
+
+async fn cmd_branches(
+    session_id: Option<&str>,
+    from_file: Option<&InputFile>,
+    refresh: bool,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    let (source, session_id) = Source::open(session_id, from_file, client_opts).await?;
+    let session = source.get_session(&session_id, refresh).await?;
+    print_branches(&session);
+    Ok(())
+}
+
+async fn cmd_read(
+    session_id: Option<&str>,
+    from_file: Option<&InputFile>,
+    filter: &EventFilter,
+    query: &EventQuery,
+    selection: Selection,
+    opts: &DisplayOptions,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    // A search with nothing that needs the whole list can print matches as
+    // pages arrive instead of holding every event in memory.
+    let streaming = filter.search.is_some() && filter.tool.is_none() && selection.is_streamable();
+    let json_events = selection.output == ReadOutput::JsonEvents;
+    // Raw and JSON output are for piping (raw also prints page cursors), so
+    // skip the progress line that would interleave with them. Streaming
+    // draws its own status line instead of the client's.
+    let progress = client_opts.progress && !opts.raw && !json_events;
+    let client_opts = ClientOptions {
+        progress: progress && !streaming,
+        ..client_opts
+    };
+    let (source, session_id) = Source::open(session_id, from_file, client_opts).await?;
+
+    if streaming {
+        // Matches print as they arrive, so the warning has to come first.
+        warn_if_live(&source.get_session(&session_id, false).await?);
+        if !opts.raw {
+            println!(
+                "\n{} ({})\n",
+                "Session Transcript".bold(),
+                transcript_labels(filter, selection)
+                    .join(" - ")
+                    .themed(Role::Accent)
+            );
+        }
+        let mut status = ScanStatus::new(progress);
+        let mut gaps = selection.gap_annotator();
+        source
+            .for_each_event(&session_id, query, |event| {
+                status.scanned += 1;
+                if filter.matches(&event) {
+                    status.matches += 1;
+                    status.clear();
+                    if let Some(line) = gaps.separator(&event, opts) {
+                        println!("{line}");
+                    }
+                    print_event(&event, opts);
+                }
+                status.draw();
+                Ok(ControlFlow::Continue(()))
+            })
+            .await?;
+        status.finish();
+        return Ok(());
+    }
+
+    if progress {
+        eprintln!("Fetching session events...");
+    }
+    let (session, events) = source.session_and_events(&session_id, query).await?;
+    warn_if_live(&session);
+    let filtered = select_events(&events, filter, selection);
+
+    if json_events {
+        println!("{}", serde_json::to_string_pretty(&filtered)?);
+        return Ok(());
+    }
+
+    let mut label_parts = vec![format!("{} events", filtered.len())];
+    label_parts.extend(transcript_labels(filter, selection));
+
+    if !opts.raw {
+        println!(
+            "\n{} ({})\n",
+            "Session Transcript".bold(),
+            label_parts.join(" - ").themed(Role::Accent)
+        );
+    }
+
+    let mut gaps = selection.gap_annotator();
+    for event in filtered {
+        if let Some(line) = gaps.separator(event, opts) {
+            println!("{line}");
+        }
+        print_event(event, opts);
+    }
+
+    Ok(())
+}
+
+/// The events `read` prints, in order: `filter`, then `--filter-tool`, then
+/// progress dedup, then the window and `--reverse`.
+fn select_events<'a>(
+    events: &'a [SessionEvent],
+    filter: &EventFilter,
+    selection: Selection,
+) -> Vec<&'a SessionEvent> {
+    let mut filtered: Vec<&SessionEvent> = events.iter().filter(|e| filter.matches(e)).collect();
+    if let Some(ref tool) = filter.tool {
+        filtered = filter_tool(filtered, tool);
+    }
+    if selection.dedup_progress {
+        filtered = dedup_progress(filtered);
+    }
+    selection.apply(&filtered).into_iter().copied().collect()
+}
+
+/// The filters and selection in effect, for the transcript header.
+fn transcript_labels(filter: &EventFilter, selection: Selection) -> Vec<String> {
+    let mut label_parts = Vec::new();
+    if filter.conversation_only {
+        label_parts.push("conversation only".to_string());
+    }
+    if filter.assistant_only {
+        label_parts.push("assistant text only".to_string());
+    }
+    if let Some(ref s) = filter.search {
+        label_parts.push(format!("search: \"{}\"", s.text));
+    }
+    if let Some(ref tool) = filter.tool {
+        label_parts.push(format!("tool: {tool}"));
+    }
+    match selection.window {
+        Window::Head(n) => label_parts.push(format!("first {n}")),
+        Window::Tail(n) => label_parts.push(format!("last {n}")),
+        Window::All => {}
+    }
+    if selection.reverse {
+        label_parts.push("newest first".to_string());
+    }
+    label_parts
+}
+
+/// "Scanned N events, M matches" on stderr while a streaming search runs.
+/// Redrawn in place on a terminal, at most every [`SCAN_REDRAW_INTERVAL`],
+/// and cleared before each match is printed; elsewhere only the final count
+/// is written.
+struct ScanStatus {
+    enabled: bool,
+    live: bool,
+    scanned: usize,
+    matches: usize,
+    /// When the line was last drawn, for throttling redraws.
+    drawn_at: Option<std::time::Instant>,
+    /// Whether the line is on screen and needs clearing before other output.
+    shown: bool,
+}
+
+/// How often the scan status line is redrawn while a search streams.
+const SCAN_REDRAW_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+impl ScanStatus {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            live: enabled && std::io::stderr().is_terminal(),
+            scanned: 0,
+            matches: 0,
+            drawn_at: None,
+            shown: false,
+        }
+    }
+
+    fn line(&self) -> String {
+        format_scan_status(self.scanned, self.matches)
+    }
+
+    /// Erases the line, if it is on screen, so a match can be printed.
+    fn clear(&mut self) {
+        if self.shown {
+            eprint!("\r\x1b[2K");
+            self.shown = false;
+        }
+    }
+
+    /// Redraws the line with the current counts, unless it was drawn less
+    /// than [`SCAN_REDRAW_INTERVAL`] ago.
+    fn draw(&mut self) {
+        if !self.live
+            || self
+                .drawn_at
+                .is_some_and(|at| at.elapsed() < SCAN_REDRAW_INTERVAL)
+        {
+            return;
+        }
+        eprint!("\r  {}", self.line());
+        self.drawn_at = Some(std::time::Instant::now());
+        self.shown = true;
+    }
+
+    /// Leaves the final counts on screen.
+    fn finish(&self) {
+        if self.live {
+            eprintln!("\r  {}", self.line());
+        } else if self.enabled {
+            eprintln!("  {}", self.line());
+        }
+    }
+}
+
+/// "Scanned 120 events, 1 match", with the noun agreeing with the count.
+fn format_scan_status(scanned: usize, matches: usize) -> String {
+    let noun = if matches == 1 { "match" } else { "matches" };
+    format!("Scanned {scanned} events, {matches} {noun}")
+}
+
+async fn cmd_event(
+    session_id: Option<&str>,
+    from_file: Option<&InputFile>,
+    event_id: &str,
+    raw: bool,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    if event_id.trim().is_empty() {
+        bail!("Event ID must not be empty");
+    }
+    let progress = client_opts.progress && !raw;
+    let client_opts = ClientOptions {
+        progress,
+        ..client_opts
+    };
+    let (source, session_id) = Source::open(session_id, from_file, client_opts).await?;
+    if progress {
+        eprintln!("Searching session events...");
+    }
+    let event = source
+        .find_event(&session_id, event_id)
+        .await?
+        .with_context(|| format!("No event with ID {event_id} in session {session_id}"))?;
+
+    if raw {
+        println!("{}", serde_json::to_string_pretty(&event)?);
+        return Ok(());
+    }
+    let opts = DisplayOptions {
+        wrap: terminal_width(),
+        full_results: true,
+        show_uuid: true,
+        pretty_input: true,
+        truncate: Some(0),
+        ..Default::default()
+    };
+    println!();
+    print_event(&event, &opts);
+    println!();
+    Ok(())
+}
+
+async fn cmd_summary(
+    session_id: Option<&str>,
+    from_file: Option<&InputFile>,
+    refresh: bool,
+    limit: usize,
+    json: bool,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    let (source, session_id) = Source::open(session_id, from_file, client_opts).await?;
+    let session = source.get_session(&session_id, refresh).await?;
+    warn_if_live(&session);
+
+    // Only API sessions are cached; a file is already local.
+    let cache_key = match &source {
+        Source::Api(_) => session.updated_at.as_deref(),
+        Source::File(_) => None,
+    };
+    let cached = cache_key
+        .filter(|_| !refresh)
+        .and_then(|key| read_cached_summary::<SessionSummary>(&session_id, key));
+    let summary = match cached {
+        Some(summary) => summary,
+        None => {
+            if client_opts.progress {
+                eprintln!("Fetching events...");
+            }
+            let events = source.get_events(&session_id, 0).await?;
+            let summary = summarize_events(&events);
+            if let Some(key) = cache_key {
+                write_cached_summary(&session_id, key, &summary);
+            }
+            summary
+        }
+    };
+
+    if json {
+        let json = SummaryJson::new(&session, &summary);
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    println!("\n{}\n", "Session Summary".bold());
+    println!(
+        "  {} ({})",
+        session.title.as_deref().unwrap_or("(untitled)").bold(),
+        status_colored(session.session_status.as_deref().unwrap_or("unknown"))
+    );
+    println!();
+    print_session_summary(&summary, limit);
+    Ok(())
+}
+
+/// Lays out `items` as tree branches, showing at most `limit` of them
+/// (0 = all) and ending with an "… and N more" branch for the rest.
+fn tree_lines(items: &[String], limit: usize) -> Vec<String> {
+    let shown = if limit == 0 {
+        items.len()
+    } else {
+        limit.min(items.len())
+    };
+    let hidden = items.len() - shown;
+    let mut lines: Vec<String> = items[..shown]
+        .iter()
+        .map(|item| format!("  \u{251c}\u{2500} {item}"))
         .collect();
+    if hidden > 0 {
+        let more = format!("\u{2026} and {hidden} more");
+        lines.push(format!("  \u{2514}\u{2500} {}", more.themed(Role::Muted)));
+    } else if let Some(last) = lines.last_mut() {
+        *last = last.replacen('\u{251c}', "\u{2514}", 1);
+    }
+    lines
+}
+
+/// Prints a summary; `limit` caps the tool summary and user message lists.
+fn print_session_summary(summary: &SessionSummary, limit: usize) {
+    for line in summary_lines(summary, limit) {
+        println!("{line}");
+    }
+}
+
+/// The lines [`print_session_summary`] prints, ending with a blank line.
+fn summary_lines(summary: &SessionSummary, limit: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    if summary.total_events == 0 {
+        lines.extend(["  (no events)".to_string(), String::new()]);
+        return lines;
+    }
+    lines.push(format!(
+        "  {}: {}",
+        "Total events".themed(Role::Muted),
+        summary.total_events
+    ));
+    for (t, c) in &summary.type_counts {
+        lines.push(format!("    {}: {c}", t.themed(Role::Muted)));
+    }
+    lines.push(String::new());
+
+    let timing = &summary.timing;
+    if let Some(wall_ms) = timing.wall_clock_ms {
+        let share = |ms: u64| match ms.checked_mul(100).and_then(|p| p.checked_div(wall_ms)) {
+            Some(pct) => format!("{} ({pct}% of wall clock)", format_duration(ms)),
+            None => format_duration(ms),
+        };
+        lines.push(format!("  {}:", "Timing".bold()));
+        lines.push(format!(
+            "    {}: {}",
+            "Wall clock".themed(Role::Muted),
+            format_duration(wall_ms)
+        ));
+        lines.push(format!(
+            "    {}: {}",
+            "Turn time".themed(Role::Muted),
+            share(timing.turn_ms)
+        ));
+        lines.push(format!(
+            "    {}: {}",
+            "API time".themed(Role::Muted),
+            share(timing.api_ms)
+        ));
+        lines.push(String::new());
+    }
+
+    // Capabilities the session was launched with
+    if !summary.capabilities.is_empty() {
+        lines.push(format!("  {}:", "Capabilities".bold()));
+        for (label, names) in &summary.capabilities {
+            lines.push(format!(
+                "    {} ({}): {}",
+                label.themed(Role::Muted),
+                names.len(),
+                names.join(", ")
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !summary.mcp_servers.is_empty() {
+        lines.push(format!(
+            "  {} ({}):",
+            "MCP Servers".bold(),
+            summary.mcp_servers.len()
+        ));
+        lines.extend(
+            tree_lines(&summary.mcp_servers, limit)
+                .into_iter()
+                .map(|line| format!("  {line}")),
+        );
+        lines.push(String::new());
+    }
+
+    let summaries = &summary.tool_summaries;
+    if !summaries.is_empty() {
+        lines.push(format!(
+            "  {} ({}):",
+            "Tool Use Summaries".bold(),
+            summaries.len()
+        ));
+        lines.extend(
+            tree_lines(summaries, limit)
+                .into_iter()
+                .map(|line| format!("  {line}")),
+        );
+    }
+
+    let messages = &summary.user_messages;
+    if !messages.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "  {} ({}):",
+            "User Messages".bold(),
+            messages.len()
+        ));
+        lines.extend(
+            tree_lines(messages, limit)
+                .into_iter()
+                .map(|line| format!("  {line}")),
+        );
+    }
+
+    lines.push(String::new());
+    lines
+}
+
+/// How `stats` lists its slowest tool calls.
+#[derive(Clone, Copy)]
+struct SlowCalls {
+    /// How many to list; 0 turns the list off.
+    count: usize,
+    /// Calls taking longer than this are highlighted.
+    threshold_secs: Option<u64>,
+}
+
+async fn cmd_stats(
+    session_id: Option<&str>,
+    from_file: Option<&InputFile>,
+    group_by: Option<GroupBy>,
+    slow: SlowCalls,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    let (source, session_id) = Source::open(session_id, from_file, client_opts).await?;
+    if client_opts.progress {
+        eprintln!("Fetching events...");
+    }
+    let (session, events) = source
+        .session_and_events(&session_id, &EventQuery::default())
+        .await?;
+    warn_if_live(&session);
+
+    println!("\n{}\n", "Tool Usage".bold());
+    for line in tool_usage_lines(&events, group_by, slow) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// The lines `stats` prints under its heading, ending with a blank line.
+fn tool_usage_lines(
+    events: &[SessionEvent],
+    group_by: Option<GroupBy>,
+    slow: SlowCalls,
+) -> Vec<String> {
+    if events.is_empty() {
+        return vec!["  (no events)".to_string(), String::new()];
+    }
+    let rows = export::tool_usage_rows(events);
+    if rows.is_empty() {
+        return vec!["  No tool calls found.".to_string(), String::new()];
+    }
+    let mut lines = Vec::new();
+    match group_by {
+        None => lines.extend(stats_bucket_lines(&stats::overall(&rows), true)),
+        Some(group_by) => {
+            // Grouping by tool already gives one line per tool.
+            let per_tool = group_by != GroupBy::Tool;
+            for bucket in stats::group_tool_usage(&rows, group_by) {
+                lines.extend(stats_bucket_lines(&bucket, per_tool));
+            }
+        }
+    }
+    lines.push(String::new());
+
+    let slowest = stats::slowest_calls(&rows, slow.count);
+    if !slowest.is_empty() {
+        lines.extend([format!("{}", "Slowest Calls".bold()), String::new()]);
+        lines.extend(slow_call_lines(&slowest, slow.threshold_secs));
+        lines.push(String::new());
+    }
+    lines
+}
+
+async fn cmd_doctor(timeouts: Timeouts) -> Result<()> {
+    let ok = "\u{2713}".themed(Role::Success);
+    let fail = "\u{2717}".themed(Role::Error);
+    println!("\n{}\n", "Doctor".bold());
+
+    let (creds, source) = load_credentials()?;
+    let token = &creds.claude_ai_oauth;
+    println!("  {ok} Credentials loaded from {source}");
+    println!("  {ok} Access token {}", redact_token(&token.access_token));
+    let api_url = client::base_url();
+    println!("  {ok} API base URL {api_url}");
+
+    let mut failures = 0;
+    match token.expires_at_utc() {
+        Some(expires) if expires > Utc::now() => {
+            println!(
+                "  {ok} Token expires {}",
+                format_timestamp(&expires.to_rfc3339())
+            );
+        }
+        Some(expires) => {
+            failures += 1;
+            println!(
+                "  {fail} Token expired {}. Run `claude login` to refresh your token.",
+                format_timestamp(&expires.to_rfc3339())
+            );
+        }
+        None => {
+            failures += 1;
+            println!(
+                "  {fail} Token has an unreadable expiresAt ({}). Run `claude login` to refresh your token.",
+                token.expires_at
+            );
+        }
+    }
+
+    match verify_token(&token.access_token, timeouts).await {
+        Ok(org_uuid) => println!("  {ok} Profile fetch succeeded, org UUID {org_uuid}"),
+        Err(err) => {
+            failures += 1;
+            println!("  {fail} Profile fetch failed: {err:#}");
+            if let Some(ApiError::Unauthorized { .. }) = err.downcast_ref::<ApiError>() {
+                println!("    Run `claude login` to refresh your token.");
+            } else {
+                println!("    Check your network connection and that {api_url} is reachable.");
+            }
+        }
+    }
+    println!();
+
+    if failures > 0 {
+        bail!("{failures} check(s) failed");
+    }
+    Ok(())
+}
+
+/// "Name (id)", or just the ID when there is no name.
+fn name_with_id(name: Option<&str>, id: &str) -> String {
+    match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => format!("{name} ({id})"),
+        None => id.to_string(),
+    }
+}
+
+async fn cmd_whoami(client_opts: ClientOptions) -> Result<()> {
+    let (creds, source) = load_credentials()?;
+    let profile = ApiClient::with_credentials(&creds, client_opts)
+        .await?
+        .get_profile()
+        .await?;
+
+    println!("\n{}\n", "Who am I".bold());
+    let account = match &profile.account {
+        Some(account) => name_with_id(
+            account.display_name.as_deref(),
+            account.uuid.as_deref().unwrap_or("unknown"),
+        ),
+        None => "(not returned by the API)".to_string(),
+    };
+    println!("  {}:      {account}", "Account".themed(Role::Muted));
+    println!(
+        "  {}: {}",
+        "Organization".themed(Role::Muted),
+        name_with_id(
+            profile.organization.name.as_deref(),
+            &profile.organization.uuid
+        )
+    );
+    // Environment tokens carry no expiry; theirs is a far-future placeholder.
+    let expires_at = match source {
+        client::CredentialSource::Env => None,
+        _ => Some(creds.claude_ai_oauth.expires_at_utc()),
+    };
+    let expiry = match expires_at {
+        None => "no expiry recorded".to_string(),
+        Some(Some(expires)) if expires > Utc::now() => {
+            format!("expires {}", format_timestamp(&expires.to_rfc3339()))
+        }
+        Some(Some(expires)) => format!(
+            "{} {}",
+            "expired".themed(Role::Error),
+            format_timestamp(&expires.to_rfc3339())
+        ),
+        Some(None) => format!(
+            "unreadable expiresAt ({})",
+            creds.claude_ai_oauth.expires_at
+        ),
+    };
+    println!("  {}:        {expiry}", "Token".themed(Role::Muted));
+    println!("  {}:       {source}\n", "Source".themed(Role::Muted));
+    Ok(())
+}
+
+fn cmd_describe(event_type: Option<&str>) -> Result<()> {
+    let Some(event_type) = event_type else {
+        println!("\n{}\n", "Event Types".bold());
+        for schema in EVENT_SCHEMAS {
+            println!(
+                "  {:18} {}",
+                schema.event_type.themed(Role::Accent),
+                schema.rust_type.themed(Role::Muted)
+            );
+        }
+        println!("\n  Run 'describe <type>' for field details.\n");
+        return Ok(());
+    };
+
+    let Some(schema) = event_schema(event_type) else {
+        let known: Vec<&str> = EVENT_SCHEMAS.iter().map(|s| s.event_type).collect();
+        bail!(
+            "Unknown event type: '{event_type}'. Known types: {}",
+            known.join(", ")
+        );
+    };
+    print_event_schema(schema);
+    Ok(())
+}
+
+fn cmd_resume(session_id: &str, launch: bool) -> Result<()> {
+    validate_session_id(session_id)?;
+
+    if !launch {
+        println!("claude --teleport {session_id}");
+        return Ok(());
+    }
+
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    let Some(claude) = find_in_path("claude", &path_var) else {
+        bail!(
+            "Could not find 'claude' on PATH. Install Claude Code, or run this manually:\n  \
+             claude --teleport {session_id}"
+        );
+    };
+
+    let status = std::process::Command::new(&claude)
+        .args(["--teleport", session_id])
+        .status()
+        .with_context(|| format!("Failed to launch {}", claude.display()))?;
+    if !status.success() {
+        bail!("claude --teleport exited with {status}");
+    }
+    Ok(())
+}
+
+/// Fetches events for each session and counts matches for `query`.
+///
+/// At most `jobs` sessions are fetched at once, each holding its permit for
+/// its whole pagination. `get_events` does not retry: a 429 or any other
+/// failed page ends that session's search, and the error is returned as its
+/// result while the other sessions carry on. Returns one result per session,
+/// in input order.
+async fn search_sessions(
+    api: Arc<ApiClient>,
+    session_ids: Vec<String>,
+    query: &TextQuery,
+    max_events: usize,
+    jobs: usize,
+    progress: bool,
+) -> Vec<Result<usize>> {
+    let query = Arc::new(query.clone());
+    run_per_session(session_ids, jobs, progress, move |id| {
+        let api = Arc::clone(&api);
+        let query = Arc::clone(&query);
+        async move {
+            let events = api.get_events(&id, max_events).await?;
+            Ok(events.iter().filter(|e| query.matches(e)).count())
+        }
+    })
+    .await
+}
+
+/// Runs `task` for each session with at most `jobs` at a time, drawing a
+/// "Searched N/M sessions" line when `progress` is set. Returns one result per
+/// session, in input order; a task that panics gives an error for its
+/// session instead of taking the others down with it.
+async fn run_per_session<F, Fut>(
+    session_ids: Vec<String>,
+    jobs: usize,
+    progress: bool,
+    task: F,
+) -> Vec<Result<usize>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<usize>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(jobs));
+    let mut set = JoinSet::new();
+    let mut positions = HashMap::new();
+    for (i, id) in session_ids.iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        let run = task(id.clone());
+        let handle = set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            run.await
+        });
+        positions.insert(handle.id(), i);
+    }
+
+    let total = session_ids.len();
+    let mut results: Vec<Result<usize>> = (0..total).map(|_| Ok(0)).collect();
+    let mut done = 0;
+    while let Some(joined) = set.join_next_with_id().await {
+        let (id, result) = match joined {
+            Ok((id, result)) => (id, result),
+            Err(err) => (err.id(), Err(anyhow::anyhow!("search task failed: {err}"))),
+        };
+        results[positions[&id]] = result;
+        done += 1;
+        if progress {
+            eprint!("\r  Searched {done}/{total} sessions...");
+        }
+    }
+    if progress && total > 0 {
+        eprintln!();
+    }
+    results
+}
+
+async fn cmd_search(
+    query: &TextQuery,
+    limit: usize,
+    status_filter: Option<String>,
+    max_events: usize,
+    jobs: usize,
+    count_only: bool,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    // Per-session page progress would interleave; only the overall count is shown.
+    let api = ApiClient::new(client_opts).await?.with_progress(false);
+    let sessions = api.list_sessions().await?;
+
+    let selected: Vec<&Session> = sessions
+        .iter()
+        .filter(|s| {
+            status_filter
+                .as_deref()
+                .is_none_or(|f| s.session_status.as_deref() == Some(f))
+        })
+        .take(limit)
+        .collect();
+
+    if client_opts.progress {
+        eprintln!(
+            "Searching {} sessions ({jobs} at a time)...",
+            selected.len()
+        );
+    }
+    let ids = selected.iter().map(|s| s.id.clone()).collect();
+    let results = search_sessions(
+        Arc::new(api),
+        ids,
+        query,
+        max_events,
+        jobs,
+        client_opts.progress,
+    )
+    .await;
+
+    if count_only {
+        let mut counts = Vec::new();
+        for (session, result) in selected.iter().zip(&results) {
+            match result {
+                Ok(n) => counts.push((session.id.as_str(), *n)),
+                Err(e) => eprintln!(
+                    "  {} {}: {e:#}",
+                    "warning:".themed(Role::Warning),
+                    session.id
+                ),
+            }
+        }
+        for line in format_match_counts(counts) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
+    let mut hits = 0;
+    for (session, result) in selected.iter().zip(&results) {
+        match result {
+            Ok(0) => {}
+            Ok(n) => {
+                hits += 1;
+                print_session_row(session);
+                println!(
+                    "    {} matching events\n",
+                    n.to_string().themed(Role::Accent)
+                );
+            }
+            Err(e) => eprintln!(
+                "  {} {}: {e:#}",
+                "warning:".themed(Role::Warning),
+                session.id
+            ),
+        }
+    }
+
+    println!(
+        "\n{} \"{}\": {hits} of {} sessions matched\n",
+        "Search".bold(),
+        query.text,
+        selected.len()
+    );
+    Ok(())
+}
+
+/// `session_id: N matches` lines for `search --count-only`, most matches
+/// first and ties broken by session ID.
+fn format_match_counts(mut counts: Vec<(&str, usize)>) -> Vec<String> {
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+        .into_iter()
+        .map(|(id, n)| format!("{id}: {n} matches"))
+        .collect()
+}
+
+async fn cmd_loglines(
+    session_id: &str,
+    filter: &LoglineFilter,
+    opts: &DisplayOptions,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    validate_session_id(session_id)?;
+    let api = ApiClient::new(client_opts).await?;
+
+    if client_opts.progress {
+        eprintln!("Fetching session loglines...");
+    }
+    let loglines = api.get_loglines(session_id).await?;
+    let shown: Vec<&Logline> = loglines.iter().filter(|l| filter.matches(l)).collect();
+
+    println!(
+        "\n{} ({} of {} loglines)\n",
+        "Session Loglines".bold(),
+        shown.len(),
+        loglines.len()
+    );
+
+    for log in shown {
+        print_logline(log, opts);
+    }
+
+    Ok(())
+}
+
+/// `export` settings beyond the session, destination, and format.
+struct ExportOptions<'a> {
+    /// JSON Pointer to write instead of the whole export.
+    select: Option<&'a str>,
+    refresh: bool,
+    best_effort: bool,
+    /// Strip thinking blocks from assistant messages before writing.
+    no_thinking: bool,
+    /// Nest tool results under their tool uses; see
+    /// [`export::flatten_tool_results`].
+    flatten_tool_results: bool,
+}
+
+/// `export` as a JSON value, with tool results nested under their tool uses
+/// when `flatten` is set.
+fn export_value(export: &SessionExport, flatten: bool) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(export)?;
+    if flatten && let Some(events) = value["events"].as_array_mut() {
+        export::flatten_tool_results(events);
+    }
+    Ok(value)
+}
+
+async fn cmd_export(
+    session_id: &str,
+    output: &str,
+    format: ExportFormat,
+    opts: &ExportOptions<'_>,
+    client_opts: ClientOptions,
+) -> Result<()> {
+    validate_session_id(session_id)?;
+    let query = EventQuery {
+        best_effort: opts.best_effort,
+        ..Default::default()
+    };
+    let select = opts.select;
+    if let Some(pointer) = select {
+        if format != ExportFormat::Json {
+            bail!("--select only applies to JSON exports");
+        }
+        export::validate_pointer(pointer)?;
+    }
+    if opts.flatten_tool_results && format != ExportFormat::Json {
+        bail!("--flatten-tool-results only applies to JSON exports");
+    }
+
+    // Validate output path
+    let path = std::path::Path::new(output);
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+        && !parent.exists()
+    {
+        bail!("Output directory does not exist: {}", parent.display());
+    }
+
+    let api = ApiClient::new(client_opts).await?;
+
+    if client_opts.progress {
+        eprintln!("Fetching session metadata...");
+    }
+    let session = api.get_session_cached(session_id, opts.refresh).await?;
+    warn_if_live(&session);
+
+    if format == ExportFormat::NdjsonEvents {
+        if client_opts.progress {
+            eprintln!("Streaming events...");
+        }
+        let file =
+            std::fs::File::create(output).with_context(|| format!("Failed to create {output}"))?;
+        let mut out = std::io::BufWriter::new(file);
+        let count = api
+            .for_each_event(session_id, &query, |mut event| {
+                if opts.no_thinking {
+                    export::strip_thinking(&mut event);
+                }
+                export::write_ndjson_line(&mut out, &event)
+                    .with_context(|| format!("Failed to write events to {output}"))?;
+                Ok(ControlFlow::Continue(()))
+            })
+            .await?;
+        out.flush()
+            .with_context(|| format!("Failed to write events to {output}"))?;
+        println!(
+            "\nExported {} events to {}\n",
+            count.to_string().themed(Role::Accent),
+            output.themed(Role::Success)
+        );
+        return Ok(());
+    }
+
+    if format == ExportFormat::Csv {
+        if client_opts.progress {
+            eprintln!("Fetching all events...");
+        }
+        let events = api.get_events_query(session_id, &query).await?;
+        let file =
+            std::fs::File::create(output).with_context(|| format!("Failed to create {output}"))?;
+        let rows = export::write_tool_usage_csv(&events, file)
+            .with_context(|| format!("Failed to write tool usage CSV to {output}"))?;
+        println!(
+            "\nExported {} tool calls to {}\n",
+            rows.to_string().themed(Role::Accent),
+            output.themed(Role::Success)
+        );
+        return Ok(());
+    }
+
+    if client_opts.progress {
+        eprintln!("Fetching all events...");
+    }
+    let mut events = api.get_events_query(session_id, &query).await?;
+    if opts.no_thinking {
+        events.iter_mut().for_each(export::strip_thinking);
+    }
+
+    if format == ExportFormat::Text {
+        let file =
+            std::fs::File::create(output).with_context(|| format!("Failed to create {output}"))?;
+        export::write_transcript_text(&session, &events, std::io::BufWriter::new(file))
+            .with_context(|| format!("Failed to write transcript to {output}"))?;
+        println!(
+            "\nExported {} events to {}\n",
+            events.len().to_string().themed(Role::Accent),
+            output.themed(Role::Success)
+        );
+        return Ok(());
+    }
+
+    let export = SessionExport {
+        session,
+        total_events: Some(events.len()),
+        events,
+        exported_at: Some(Utc::now().to_rfc3339()),
+    };
+
+    if let Some(pointer) = select {
+        let value = export_value(&export, opts.flatten_tool_results)?;
+        let json = serde_json::to_string_pretty(export::select_pointer(&value, pointer)?)?;
+        std::fs::write(output, &json)
+            .with_context(|| format!("Failed to write export to {output}"))?;
+        println!(
+            "\nExported {} to {} ({}, {} lines)\n",
+            pointer.themed(Role::Accent),
+            output.themed(Role::Success),
+            format_bytes(json.len() as u64),
+            json.lines().count()
+        );
+        return Ok(());
+    }
+
+    let sizes = export::content_sizes(&export.events);
+    let json = if opts.flatten_tool_results {
+        serde_json::to_string_pretty(&export_value(&export, true)?)?
+    } else {
+        serde_json::to_string_pretty(&export)?
+    };
+    std::fs::write(output, &json).with_context(|| format!("Failed to write export to {output}"))?;
+    println!(
+        "\nExported {} events to {} ({}, {} lines)",
+        export.events.len().to_string().themed(Role::Accent),
+        output.themed(Role::Success),
+        format_bytes(json.len() as u64),
+        json.lines().count()
+    );
+    println!(
+        "  {}: {}  {}: {}\n",
+        "Assistant text".themed(Role::Muted),
+        format_bytes(sizes.assistant_text as u64),
+        "Tool output".themed(Role::Muted),
+        format_bytes(sizes.tool_output as u64)
+    );
+
+    Ok(())
+}
+
+// ── Main ─────────────────────────────────────────────────────────────
+
+/// Restores the default SIGPIPE action. Rust ignores SIGPIPE, so once a
+/// reader like `head` closes the pipe, `println!` would panic; with the
+/// default action the process just ends quietly, like other Unix tools.
+fn reset_sigpipe() {
+    #[cfg(unix)]
+    // SAFETY: `signal` with SIG_DFL installs no handler and touches no Rust state.
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+}
+
+/// Set while `list --watch` runs; Ctrl-C then ends the watch through
+/// [`WATCH_INTERRUPT`] instead of exiting.
+static WATCHING: AtomicBool = AtomicBool::new(false);
+static WATCH_INTERRUPT: Notify = Notify::const_new();
+
+/// The Ctrl-C handler. It runs on its own thread, so it fires even while the
+/// main thread is busy printing or blocked on a write.
+fn on_interrupt() {
+    if WATCHING.load(Ordering::SeqCst) {
+        WATCH_INTERRUPT.notify_one();
+        return;
+    }
+    restore_terminal();
+    std::process::exit(exit_code::INTERRUPTED.into());
+}
+
+/// Resets colors and ends the line after Ctrl-C, so a write cut off inside a
+/// colored span doesn't leave the shell prompt colored. Only touches
+/// terminals; piped output is left as is.
+fn restore_terminal() {
+    if std::io::stdout().is_terminal() {
+        write_unlocked(1, b"\x1b[0m\n");
+    }
+    if std::io::stderr().is_terminal() {
+        write_unlocked(2, b"\x1b[0m");
+    }
+}
+
+/// Writes straight to a file descriptor. The interrupted main thread may be
+/// holding the stdout lock, so `println!` here could deadlock.
+#[cfg(unix)]
+fn write_unlocked(fd: i32, bytes: &[u8]) {
+    // SAFETY: `bytes` is a live buffer of `bytes.len()` bytes; a short or
+    // failed write only loses the reset.
+    unsafe {
+        libc::write(fd, bytes.as_ptr().cast(), bytes.len());
+    }
+}
+
+#[cfg(not(unix))]
+fn write_unlocked(fd: i32, bytes: &[u8]) {
+    let _ = if fd == 1 {
+        std::io::stdout().write_all(bytes)
+    } else {
+        std::io::stderr().write_all(bytes)
+    };
+}
+
+/// Process exit codes, one per failure class scripts may want to tell apart.
+/// 2 is left to clap, which uses it for usage errors.
+mod exit_code {
+    pub const FAILURE: u8 = 1;
+    pub const AUTH: u8 = 3;
+    pub const NOT_FOUND: u8 = 4;
+    pub const RATE_LIMITED: u8 = 5;
+    pub const NETWORK: u8 = 6;
+    pub const SERVER: u8 = 7;
+    /// 128 + SIGINT, as shells report a Ctrl-C.
+    pub const INTERRUPTED: u8 = 130;
+}
+
+/// Picks the exit code for an error from `run` by looking through its chain
+/// for an `ApiError` or a transport-level reqwest failure.
+fn exit_code_for(err: &anyhow::Error) -> u8 {
+    for cause in err.chain() {
+        if let Some(api_err) = cause.downcast_ref::<ApiError>() {
+            return match api_err {
+                ApiError::Unauthorized { .. } => exit_code::AUTH,
+                ApiError::NotFound { .. } => exit_code::NOT_FOUND,
+                ApiError::RateLimited { .. } => exit_code::RATE_LIMITED,
+                ApiError::Server { .. } => exit_code::SERVER,
+                ApiError::Other { .. } => exit_code::FAILURE,
+            };
+        }
+        if let Some(req_err) = cause.downcast_ref::<reqwest::Error>()
+            && (req_err.is_connect() || req_err.is_timeout() || req_err.is_request())
+        {
+            return exit_code::NETWORK;
+        }
+    }
+    exit_code::FAILURE
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    reset_sigpipe();
+    let cli = Cli::parse();
+    theme::set_color_mode(cli.color);
+    theme::set_theme(cli.theme.or_else(ThemeName::from_env).unwrap_or_default());
+    init_logging(cli.verbose, cli.color);
+    if let Err(err) = ctrlc::set_handler(on_interrupt) {
+        eprintln!("Warning: failed to install the Ctrl-C handler: {err}");
+    }
+    let Err(err) = run(cli).await else {
+        return ExitCode::SUCCESS;
+    };
+    // Same format as returning the error from main, plus a hint for 401s.
+    eprintln!("Error: {err:?}");
+    if let Some(ApiError::Unauthorized { .. }) = err.downcast_ref::<ApiError>() {
+        eprintln!(
+            "{}",
+            "Run `claude login` to refresh your token (or set a new CLAUDE_ACCESS_TOKEN)."
+                .themed(Role::Warning)
+        );
+    }
+    ExitCode::from(exit_code_for(&err))
+}
+
+/// Which `tracing` output `-v` enables: `-v` shows this crate's debug
+/// logs, `-vv` adds its trace logs and the HTTP stack's debug logs
+/// (connections, TLS). Warnings always pass.
+fn log_filter(verbose: u8) -> Targets {
+    let (ours, deps) = match verbose {
+        0 => (LevelFilter::WARN, LevelFilter::WARN),
+        1 => (LevelFilter::DEBUG, LevelFilter::WARN),
+        _ => (LevelFilter::TRACE, LevelFilter::DEBUG),
+    };
+    Targets::new()
+        .with_default(deps)
+        .with_target(env!("CARGO_CRATE_NAME"), ours)
+}
+
+/// Sends `tracing` output to stderr, filtered by [`log_filter`] and colored
+/// as `--color` and the theme allow.
+fn init_logging(verbose: u8, color: ColorMode) {
+    let ansi = theme::stderr_colors(color);
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(false)
+                .with_writer(std::io::stderr)
+                .with_ansi(ansi),
+        )
+        .with(log_filter(verbose))
+        .init();
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let input_file = InputFile::from_flags(cli.from_file, cli.from_jsonl);
+    let from_file = input_file.as_ref();
+    let client_opts = ClientOptions {
+        timeouts: Timeouts::resolve(cli.timeout, cli.connect_timeout)?,
+        page_size: cli.page_size,
+        // Log lines would break into the progress spinner's line, and say more.
+        progress: !cli.no_progress && cli.verbose == 0,
+    };
+
+    match cli.command {
+        Commands::List {
+            limit,
+            status,
+            after,
+            since,
+            before,
+            model,
+            repo,
+            sort,
+            reverse,
+            json,
+            fields,
+            compact,
+            pretty,
+            watch,
+            columns,
+        } => {
+            require_api("list", from_file)?;
+            let filter = SessionFilter {
+                status,
+                after: after
+                    .as_deref()
+                    .map(|s| parse_date_filter(s, false))
+                    .transpose()?,
+                before: before
+                    .as_deref()
+                    .map(|s| parse_date_filter(s, true))
+                    .transpose()?,
+                model,
+                repo,
+            };
+            let output = match (json, watch) {
+                (true, _) => ListOutput::Json {
+                    fields,
+                    pretty: pretty_json(pretty, compact, std::io::stdout().is_terminal()),
+                },
+                (false, Some(secs)) => ListOutput::Watch {
+                    interval: std::time::Duration::from_secs(secs),
+                },
+                (false, None) if !columns.is_empty() => ListOutput::Columns(columns),
+                (false, None) => ListOutput::Table,
+            };
+            cmd_list(
+                limit,
+                filter,
+                since.as_deref(),
+                sort,
+                reverse,
+                output,
+                client_opts,
+            )
+            .await
+        }
+        Commands::Show {
+            session_id,
+            full,
+            refresh,
+        } => cmd_show(session_id.as_deref(), from_file, full, refresh, client_opts).await,
+        Commands::Branches {
+            session_id,
+            refresh,
+        } => cmd_branches(session_id.as_deref(), from_file, refresh, client_opts).await,
+        Commands::Read {
+            session_id,
+            conversation_only,
+            assistant_only,
+            r#type,
+            max_events,
+            after_id,
+            before_id,
+            best_effort,
+            head,
+            tail,
+            reverse,
+            search,
+            search_fields,
+            filter_tool,
+            min_level,
+            category,
+            exclude_replay,
+            only_errors,
+            wrap,
+            compact,
+            full_results,
+            show_uuid,
+            relative_time,
+            dedup_progress,
+            pretty_input,
+            truncate,
+            trim,
+            raw,
+            json_events,
+            annotate_gaps,
+        } => {
+            let filter = EventFilter {
+                conversation_only,
+                assistant_only,
+                types: r#type,
+                search: search.map(|text| TextQuery {
+                    text,
+                    fields: search_fields,
+                }),
+                min_level,
+                category,
+                exclude_replay,
+                only_errors,
+                tool: filter_tool,
+            };
+            let opts = DisplayOptions {
+                wrap: wrap.unwrap_or_else(terminal_width),
+                compact,
+                full_results,
+                plain: false,
+                show_uuid,
+                relative_to: relative_time.then(Utc::now),
+                pretty_input,
+                truncate,
+                text_only: assistant_only,
+                trim,
+                raw,
+            };
+            cmd_read(
+                session_id.as_deref(),
+                from_file,
+                &filter,
+                &EventQuery {
+                    after_id,
+                    before_id,
+                    max_events,
+                    best_effort,
+                    log_cursors: raw,
+                },
+                Selection {
+                    window: Window::new(head, tail),
+                    reverse,
+                    dedup_progress,
+                    output: if json_events {
+                        ReadOutput::JsonEvents
+                    } else {
+                        ReadOutput::Transcript { annotate_gaps }
+                    },
+                },
+                &opts,
+                client_opts,
+            )
+            .await
+        }
+        Commands::Summary {
+            session_id,
+            refresh,
+            summary_limit,
+            json,
+        } => {
+            cmd_summary(
+                session_id.as_deref(),
+                from_file,
+                refresh,
+                summary_limit,
+                json,
+                client_opts,
+            )
+            .await
+        }
+        Commands::Event {
+            session_id,
+            event_id,
+            raw,
+        } => {
+            cmd_event(
+                session_id.as_deref(),
+                from_file,
+                &event_id,
+                raw,
+                client_opts,
+            )
+            .await
+        }
+        Commands::Resume { session_id, launch } => {
+            require_api("resume", from_file)?;
+            cmd_resume(&session_id, launch)
+        }
+        Commands::Describe { event_type } => cmd_describe(event_type.as_deref()),
+        Commands::Stats {
+            session_id,
+            group_by,
+            slowest,
+            slow_threshold,
+        } => {
+            cmd_stats(
+                session_id.as_deref(),
+                from_file,
+                group_by,
+                SlowCalls {
+                    count: slowest,
+                    threshold_secs: slow_threshold,
+                },
+                client_opts,
+            )
+            .await
+        }
+        Commands::Search {
+            query,
+            search_fields,
+            limit,
+            status,
+            max_events,
+            jobs,
+            count_only,
+        } => {
+            require_api("search", from_file)?;
+            let query = TextQuery {
+                text: query,
+                fields: search_fields,
+            };
+            cmd_search(
+                &query,
+                limit,
+                status,
+                max_events,
+                jobs as usize,
+                count_only,
+                client_opts,
+            )
+            .await
+        }
+        Commands::Loglines {
+            session_id,
+            main_only,
+            meta,
+            full,
+            grep,
+            r#type,
+            after,
+            before,
+        } => {
+            require_api("loglines", from_file)?;
+            let filter = LoglineFilter {
+                main_only,
+                meta,
+                needle: grep.map(|g| g.to_lowercase()),
+                types: r#type,
+                after: after
+                    .as_deref()
+                    .map(|s| parse_date_filter(s, false))
+                    .transpose()?,
+                before: before
+                    .as_deref()
+                    .map(|s| parse_date_filter(s, true))
+                    .transpose()?,
+            };
+            let opts = DisplayOptions {
+                wrap: terminal_width(),
+                full_results: full,
+                ..Default::default()
+            };
+            cmd_loglines(&session_id, &filter, &opts, client_opts).await
+        }
+        Commands::Export {
+            session_id,
+            output,
+            format,
+            select,
+            refresh,
+            best_effort,
+            no_thinking,
+            flatten_tool_results,
+        } => {
+            require_api("export", from_file)?;
+            let output = output.as_deref().unwrap_or(format.default_output());
+            let opts = ExportOptions {
+                select: select.as_deref(),
+                refresh,
+                best_effort,
+                no_thinking,
+                flatten_tool_results,
+            };
+            cmd_export(&session_id, output, format, &opts, client_opts).await
+        }
+        Commands::Whoami => {
+            require_api("whoami", from_file)?;
+            cmd_whoami(client_opts).await
+        }
+        Commands::Doctor => {
+            require_api("doctor", from_file)?;
+            cmd_doctor(client_opts.timeouts).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn log_filter_keeps_dependencies_quiet_until_vv() {
+        use tracing::Level;
+        let ours = concat!(env!("CARGO_CRATE_NAME"), "::client");
+        let quiet = log_filter(0);
+        assert!(!quiet.would_enable(ours, &Level::DEBUG));
+        assert!(quiet.would_enable("hyper_util", &Level::WARN));
+
+        let v = log_filter(1);
+        assert!(v.would_enable(ours, &Level::DEBUG));
+        assert!(!v.would_enable(ours, &Level::TRACE));
+        assert!(!v.would_enable("hyper_util", &Level::DEBUG));
+
+        let vv = log_filter(2);
+        assert!(vv.would_enable(ours, &Level::TRACE));
+        assert!(vv.would_enable("hyper_util", &Level::DEBUG));
+        assert!(!vv.would_enable("hyper_util", &Level::TRACE));
+    }
+
+    // ── project_session ─────────────────────────────────────────────
+
+    #[test]
+    fn session_fields_match_serialized_keys() {
+        let session: Session =
+            serde_json::from_value(serde_json::json!({ "id": "session_01x" })).unwrap();
+        let value = serde_json::to_value(&session).unwrap();
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut expected = SESSION_FIELDS.to_vec();
+        let mut keys = keys;
+        expected.sort();
+        keys.sort();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn project_session_keeps_requested_fields() {
+        let session: Session = serde_json::from_value(serde_json::json!({
+            "id": "session_01x",
+            "title": "Fix bug",
+            "session_status": "idle"
+        }))
+        .unwrap();
+        let fields = vec!["title".to_string(), "id".to_string()];
+        let projected = project_session(&session, &fields).unwrap();
+        assert_eq!(
+            projected,
+            serde_json::json!({ "title": "Fix bug", "id": "session_01x" })
+        );
+    }
+
+    #[test]
+    fn project_session_empty_fields_keeps_all() {
+        let session: Session =
+            serde_json::from_value(serde_json::json!({ "id": "session_01x" })).unwrap();
+        let projected = project_session(&session, &[]).unwrap();
+        assert_eq!(projected.as_object().unwrap().len(), SESSION_FIELDS.len());
+    }
+
+    #[test]
+    fn validate_session_fields_lists_valid_names() {
+        assert!(validate_session_fields(&["id".to_string(), "type".to_string()]).is_ok());
+        let err = validate_session_fields(&["nope".to_string()]).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("'nope'"));
+        assert!(msg.contains("session_status"));
+    }
+
+    // ── pretty_json ─────────────────────────────────────────────────
+
+    #[test]
+    fn pretty_json_follows_terminal_unless_flagged() {
+        assert!(pretty_json(false, false, true));
+        assert!(!pretty_json(false, false, false));
+        assert!(!pretty_json(false, true, true));
+        assert!(pretty_json(true, false, false));
+    }
+
+    // ── status_change ───────────────────────────────────────────────
+
+    #[test]
+    fn status_change_only_for_known_sessions_with_new_status() {
+        let session = |id: &str, status: &str| -> Session {
+            serde_json::from_value(serde_json::json!({ "id": id, "session_status": status }))
+                .unwrap()
+        };
+        let previous: HashMap<String, Option<String>> = [
+            ("a".to_string(), Some("running".to_string())),
+            ("b".to_string(), Some("idle".to_string())),
+            ("c".to_string(), None),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            status_change(&previous, &session("a", "completed")),
+            Some(Some("running"))
+        );
+        assert_eq!(status_change(&previous, &session("b", "idle")), None);
+        assert_eq!(
+            status_change(&previous, &session("c", "running")),
+            Some(None)
+        );
+        assert_eq!(status_change(&previous, &session("new", "running")), None);
+    }
+
+    // ── SessionFilter ───────────────────────────────────────────────
+
+    fn session_with_model(model: Option<&str>) -> Session {
+        serde_json::from_value(serde_json::json!({
+            "id": "session_01x",
+            "session_context": model.map(|m| serde_json::json!({ "model": m })),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn session_filter_default_matches_everything() {
+        assert!(SessionFilter::default().matches(&session_with_model(None)));
+    }
+
+    #[test]
+    fn session_filter_model_is_case_insensitive_substring() {
+        let filter = SessionFilter {
+            model: Some("OPUS".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&session_with_model(Some("claude-opus-4-20250514"))));
+        assert!(!filter.matches(&session_with_model(Some("claude-sonnet-4-20250514"))));
+        assert!(!filter.matches(&session_with_model(None)));
+    }
+
+    #[test]
+    fn session_filter_repo_matches_sources_and_outcomes() {
+        let session: Session = serde_json::from_value(serde_json::json!({
+            "id": "session_01x",
+            "session_context": {
+                "sources": [{ "type": "git", "url": "https://github.com/Acme/Widgets" }],
+                "outcomes": [{ "type": "git", "git_info": { "repo": "acme/gadgets" } }]
+            }
+        }))
+        .unwrap();
+        let filter = |repo: &str| SessionFilter {
+            repo: Some(repo.to_string()),
+            ..Default::default()
+        };
+        assert!(filter("acme/widgets").matches(&session));
+        assert!(filter("GADGETS").matches(&session));
+        assert!(!filter("other").matches(&session));
+        assert!(!filter("acme").matches(&session_with_model(None)));
+    }
+
+    #[test]
+    fn session_filter_status_and_dates() {
+        let session: Session = serde_json::from_value(serde_json::json!({
+            "id": "session_01x",
+            "session_status": "running",
+            "created_at": "2025-06-15T12:00:00Z"
+        }))
+        .unwrap();
+        let filter = SessionFilter {
+            status: Some("running".to_string()),
+            after: Some(parse_date_filter("2025-06-15", false).unwrap()),
+            before: Some(parse_date_filter("2025-06-15", true).unwrap()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&session));
+        let filter = SessionFilter {
+            status: Some("idle".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&session));
+    }
+
+    // ── init_system_event ───────────────────────────────────────────
+
+    #[test]
+    fn init_system_event_prefers_init_subtype() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "user", "message": { "content": "hi" } },
+            { "type": "system", "subtype": "status", "model": "a" },
+            { "type": "system", "subtype": "init", "model": "b" }
+        ]))
+        .unwrap();
+        assert_eq!(
+            init_system_event(&events).unwrap().model.as_deref(),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn init_system_event_falls_back_to_first_system() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "system", "subtype": "status", "model": "a" }
+        ]))
+        .unwrap();
+        assert_eq!(
+            init_system_event(&events).unwrap().model.as_deref(),
+            Some("a")
+        );
+        assert!(init_system_event(&[]).is_none());
+    }
+
+    // ── summarize_events ────────────────────────────────────────────
+
+    #[test]
+    fn tree_lines_caps_items_with_a_more_branch() {
+        let items: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+        assert_eq!(tree_lines(&items, 0), ["  ├─ a", "  ├─ b", "  └─ c"]);
+        assert_eq!(tree_lines(&items, 3), tree_lines(&items, 0));
+        let capped = tree_lines(&items, 1);
+        assert_eq!(capped.len(), 2);
+        assert_eq!(capped[0], "  ├─ a");
+        assert!(capped[1].starts_with("  └─ ") && capped[1].contains("… and 2 more"));
+        assert!(tree_lines(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn tree_lines_handles_empty_and_single_item_lists() {
+        assert!(tree_lines(&[], 0).is_empty());
+        let one = vec!["only".to_string()];
+        assert_eq!(tree_lines(&one, 0), ["  └─ only"]);
+        assert_eq!(tree_lines(&one, 1), ["  └─ only"]);
+        assert_eq!(tree_lines(&one, 5), ["  └─ only"]);
+    }
+
+    #[test]
+    fn summarize_events_of_empty_session_is_empty() {
+        let summary = summarize_events(&[]);
+        assert_eq!(summary, SessionSummary::default());
+        assert_eq!(summary_lines(&summary, 20), ["  (no events)", ""]);
+        let session = make_session("s1", None, None);
+        let json = serde_json::to_value(SummaryJson::new(&session, &summary)).unwrap();
+        assert_eq!(json["total_events"], 0);
+        assert_eq!(json["type_counts"], serde_json::json!({}));
+        assert_eq!(json["wall_clock_ms"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn summarize_events_counts_types_and_survives_a_cache_roundtrip() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            {
+                "type": "system",
+                "subtype": "init",
+                "tools": ["Bash", "Read"],
+                "mcp_servers": [{ "name": "github", "status": "connected" }]
+            },
+            { "type": "user", "message": { "content": "x".repeat(130) } },
+            { "type": "tool_use_summary", "summary": "Ran tests" },
+            { "type": "user", "message": { "content": "thanks" } }
+        ]))
+        .unwrap();
+        let summary = summarize_events(&events);
+        assert_eq!(summary.total_events, 4);
+        assert_eq!(
+            summary.type_counts,
+            vec![
+                ("user".to_string(), 2),
+                ("system".to_string(), 1),
+                ("tool_use_summary".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            summary.capabilities,
+            vec![(
+                "Tools".to_string(),
+                vec!["Bash".to_string(), "Read".to_string()]
+            )]
+        );
+        assert_eq!(summary.mcp_servers, vec!["github [connected]"]);
+        assert_eq!(summary.tool_summaries, vec!["Ran tests"]);
+        assert_eq!(summary.user_messages[0], format!("{}...", "x".repeat(120)));
+
+        let json = serde_json::to_string(&summary).unwrap();
+        let restored: SessionSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, summary);
+    }
+
+    #[test]
+    fn summarize_events_breaks_count_ties_by_type_name() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "tool_use_summary", "summary": "a" },
+            { "type": "result" },
+            { "type": "system" },
+            { "type": "user", "message": { "content": "hi" } },
+            { "type": "user", "message": { "content": "again" } },
+            { "type": "assistant", "message": { "content": [] } }
+        ]))
+        .unwrap();
+        let names = |events: &[SessionEvent]| {
+            summarize_events(events)
+                .type_counts
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+        let expected = ["user", "assistant", "result", "system", "tool_use_summary"];
+        assert_eq!(names(&events), expected);
+
+        let reversed: Vec<SessionEvent> = events.iter().rev().cloned().collect();
+        assert_eq!(names(&reversed), expected);
+    }
+
+    #[test]
+    fn summary_json_has_headline_fields() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "user", "message": { "content": "hi" }, "created_at": "2025-01-01T00:00:00Z" },
+            { "type": "tool_use_summary", "summary": "Read a file" },
+            { "type": "user", "message": { "content": "again" }, "created_at": "2025-01-01T00:00:05Z" }
+        ]))
+        .unwrap();
+        let mut session = make_session("s1", Some("Fix it"), None);
+        session.session_status = Some("completed".to_string());
+        let summary = summarize_events(&events);
+        let json = serde_json::to_value(SummaryJson::new(&session, &summary)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "title": "Fix it",
+                "status": "completed",
+                "total_events": 3,
+                "type_counts": { "tool_use_summary": 1, "user": 2 },
+                "tool_summaries": ["Read a file"],
+                "user_message_count": 2,
+                "wall_clock_ms": 5000
+            })
+        );
+    }
+
+    // ── tool_usage_lines ────────────────────────────────────────────
+
+    #[test]
+    fn tool_usage_lines_explain_empty_results() {
+        let slow = SlowCalls {
+            count: 5,
+            threshold_secs: None,
+        };
+        assert_eq!(tool_usage_lines(&[], None, slow), ["  (no events)", ""]);
+        assert_eq!(
+            tool_usage_lines(&[make_user_event("hi")], Some(GroupBy::Tool), slow),
+            ["  No tool calls found.", ""]
+        );
+    }
+
+    // ── logline_passes ──────────────────────────────────────────────
+
+    fn logline(is_meta: Option<bool>, is_sidechain: Option<bool>) -> Logline {
+        serde_json::from_value(serde_json::json!({
+            "type": "user",
+            "isMeta": is_meta,
+            "isSidechain": is_sidechain,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn logline_main_only_drops_sidechains() {
+        let side = logline(None, Some(true));
+        let main = logline(None, Some(false));
+        assert!(!logline_passes(&side, true, MetaMode::Include));
+        assert!(logline_passes(&main, true, MetaMode::Include));
+        assert!(logline_passes(&side, false, MetaMode::Include));
+    }
+
+    #[test]
+    fn logline_meta_modes() {
+        let meta = logline(Some(true), None);
+        let plain = logline(None, None);
+        assert!(logline_passes(&meta, false, MetaMode::Include));
+        assert!(!logline_passes(&meta, false, MetaMode::Exclude));
+        assert!(logline_passes(&plain, false, MetaMode::Exclude));
+        assert!(logline_passes(&meta, false, MetaMode::Only));
+        assert!(!logline_passes(&plain, false, MetaMode::Only));
+    }
+
+    #[test]
+    fn logline_grep_is_case_insensitive_on_content() {
+        let log: Logline = serde_json::from_value(serde_json::json!({
+            "type": "system",
+            "content": "npm ERR! network timeout",
+        }))
+        .unwrap();
+        assert!(logline_matches(&log, "err! network"));
+        assert!(!logline_matches(&log, "eaddrinuse"));
+        assert!(!logline_matches(&logline(None, None), "err"));
+    }
+
+    #[test]
+    fn logline_filter_windows_by_timestamp_and_keeps_untimed() {
+        let at = |ts: Option<&str>| -> Logline {
+            serde_json::from_value(serde_json::json!({ "type": "system", "timestamp": ts }))
+                .unwrap()
+        };
+        let filter = LoglineFilter {
+            after: Some(parse_date_filter("2025-06-15T10:00:00Z", false).unwrap()),
+            before: Some(parse_date_filter("2025-06-15", true).unwrap()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&at(Some("2025-06-15T09:59:59Z"))));
+        assert!(filter.matches(&at(Some("2025-06-15T10:00:00Z"))));
+        assert!(filter.matches(&at(Some("2025-06-15T23:59:59Z"))));
+        assert!(!filter.matches(&at(Some("2025-06-16T00:00:00Z"))));
+        assert!(filter.matches(&at(None)));
+    }
+
+    #[test]
+    fn logline_filter_keeps_listed_types() {
+        let filter = LoglineFilter {
+            types: vec!["system".to_string(), "assistant".to_string()],
+            ..Default::default()
+        };
+        assert!(!filter.matches(&logline(None, None)));
+        let system: Logline =
+            serde_json::from_value(serde_json::json!({ "type": "system" })).unwrap();
+        assert!(filter.matches(&system));
+        let untyped: Logline = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(!filter.matches(&untyped));
+        assert!(LoglineFilter::default().matches(&untyped));
+    }
+
+    // ── name_with_id ────────────────────────────────────────────────
+
+    #[test]
+    fn name_with_id_falls_back_to_id() {
+        assert_eq!(name_with_id(Some("Acme"), "org-1"), "Acme (org-1)");
+        assert_eq!(name_with_id(Some("  "), "org-1"), "org-1");
+        assert_eq!(name_with_id(None, "org-1"), "org-1");
+    }
+
+    // ── redact_token ────────────────────────────────────────────────
+
+    #[test]
+    fn redact_token_keeps_only_ends() {
+        let token = "sk-ant-REDACTED";
+        let redacted = redact_token(token);
+        assert_eq!(redacted, "sk-ant-oat...wxyz");
+        assert!(!redacted.contains("abcdefghijklmnop"));
+    }
+
+    #[test]
+    fn redact_token_short_is_fully_hidden() {
+        assert_eq!(redact_token("short-token"), "[REDACTED]");
+        assert_eq!(redact_token(""), "[REDACTED]");
+    }
+
+    // ── since_bound ─────────────────────────────────────────────────
+
+    #[test]
+    fn since_bound_excludes_the_reference_session() {
+        let reference = Session {
+            id: "session_01Reference".to_string(),
+            created_at: Some("2025-03-01T12:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let filter = SessionFilter {
+            after: Some(since_bound(&reference).unwrap()),
+            ..Default::default()
+        };
+        let later = Session {
+            created_at: Some("2025-03-01T12:00:01Z".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&reference));
+        assert!(filter.matches(&later));
+    }
+
+    #[test]
+    fn since_bound_needs_created_at() {
+        let err = since_bound(&Session {
+            id: "session_01NoDate".to_string(),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("no created_at"));
+    }
+
+    // ── parse_date_filter ───────────────────────────────────────────
+
+    #[test]
+    fn parse_date_filter_iso8601() {
+        let dt = parse_date_filter("2025-06-15T14:30:00Z", false).unwrap();
+        assert_eq!(dt.format("%Y-%m-%d").to_string(), "2025-06-15");
+    }
+
+    #[test]
+    fn parse_date_filter_date_only() {
+        let dt = parse_date_filter("2025-06-15", false).unwrap();
+        assert_eq!(
+            dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2025-06-15 00:00:00"
+        );
+    }
 
-    println!(
-        "\n{} ({} total, showing {})\n",
-        "Remote Sessions".bold(),
-        sessions.len(),
-        filtered.len()
-    );
+    #[test]
+    fn parse_date_filter_date_only_end_of_day() {
+        let dt = parse_date_filter("2025-06-15", true).unwrap();
+        assert_eq!(
+            dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2025-06-15 23:59:59"
+        );
+        let late = "2025-06-15T23:59:59.5Z".parse::<DateTime<Utc>>().unwrap();
+        assert!(late <= dt);
+    }
 
-    for s in &filtered {
-        print_session_row(s);
+    #[test]
+    fn parse_date_filter_iso8601_ignores_end_of_day() {
+        let dt = parse_date_filter("2025-06-15T14:30:00Z", true).unwrap();
+        assert_eq!(dt.format("%H:%M:%S").to_string(), "14:30:00");
     }
 
-    Ok(())
-}
+    #[test]
+    fn parse_date_filter_invalid() {
+        let err = parse_date_filter("not-a-date", false).unwrap_err();
+        assert!(err.to_string().contains("Invalid date format"));
+    }
 
-async fn cmd_show(session_id: &str) -> Result<()> {
-    validate_session_id(session_id)?;
-    let api = ApiClient::new().await?;
-    let session = api.get_session(session_id).await?;
-    print_session_detail(&session);
-    Ok(())
-}
+    // ── Window ──────────────────────────────────────────────────────
 
-async fn cmd_read(
-    session_id: &str,
-    conversation_only: bool,
-    type_filter: Option<String>,
-    max_events: usize,
-    search: Option<String>,
-) -> Result<()> {
-    validate_session_id(session_id)?;
-    let api = ApiClient::new().await?;
+    #[test]
+    fn window_head_and_tail() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(Window::All.apply(&items), &[1, 2, 3, 4, 5]);
+        assert_eq!(Window::Head(2).apply(&items), &[1, 2]);
+        assert_eq!(Window::Tail(2).apply(&items), &[4, 5]);
+    }
 
-    eprintln!("Fetching session events...");
-    let events = api.get_events(session_id, max_events).await?;
+    #[test]
+    fn window_larger_than_items() {
+        let items = [1, 2];
+        assert_eq!(Window::Head(10).apply(&items), &[1, 2]);
+        assert_eq!(Window::Tail(10).apply(&items), &[1, 2]);
+        assert!(Window::Tail(0).apply(&items).is_empty());
+    }
 
-    let filtered: Vec<&SessionEvent> = events
-        .iter()
-        .filter(|e| {
-            if let Some(ref tf) = type_filter
-                && e.event_type() != tf.as_str()
-            {
-                return false;
+    #[test]
+    fn only_plain_forward_selection_streams() {
+        let all = Selection {
+            window: Window::All,
+            reverse: false,
+            dedup_progress: false,
+            output: ReadOutput::Transcript {
+                annotate_gaps: Some(60),
+            },
+        };
+        assert!(all.is_streamable());
+        assert!(
+            !Selection {
+                window: Window::Tail(5),
+                ..all
             }
-            if conversation_only && !e.is_conversation() {
-                return false;
+            .is_streamable()
+        );
+        assert!(
+            !Selection {
+                reverse: true,
+                ..all
             }
-            if let Some(ref needle) = search
-                && !event_contains_text(e, needle)
-            {
-                return false;
+            .is_streamable()
+        );
+        assert!(
+            !Selection {
+                dedup_progress: true,
+                ..all
             }
-            true
-        })
-        .collect();
+            .is_streamable()
+        );
+        assert!(
+            !Selection {
+                output: ReadOutput::JsonEvents,
+                ..all
+            }
+            .is_streamable()
+        );
+    }
 
-    let mut label_parts = vec![format!("{} events", filtered.len())];
-    if conversation_only {
-        label_parts.push("conversation only".to_string());
+    #[test]
+    fn scan_status_counts() {
+        assert_eq!(format_scan_status(120, 1), "Scanned 120 events, 1 match");
+        assert_eq!(format_scan_status(0, 0), "Scanned 0 events, 0 matches");
     }
-    if let Some(ref s) = search {
-        label_parts.push(format!("search: \"{s}\""));
+
+    #[test]
+    fn selection_reverses_after_windowing() {
+        let items = [1, 2, 3, 4, 5];
+        let tail = Selection {
+            window: Window::Tail(2),
+            reverse: true,
+            dedup_progress: false,
+            output: ReadOutput::Transcript {
+                annotate_gaps: None,
+            },
+        };
+        assert_eq!(tail.apply(&items), vec![&5, &4]);
+        let head = Selection {
+            window: Window::Head(2),
+            ..tail
+        };
+        assert_eq!(head.apply(&items), vec![&2, &1]);
+        let all = Selection {
+            window: Window::All,
+            reverse: false,
+            ..tail
+        };
+        assert_eq!(all.apply(&items).len(), 5);
     }
 
-    println!(
-        "\n{} ({})\n",
-        "Session Transcript".bold(),
-        label_parts.join(" - ").cyan()
-    );
+    #[test]
+    fn json_events_serializes_the_filtered_window() {
+        let mut events = events_with_uuids(&["a", "b", "c", "d"]);
+        events.insert(2, make_summary_event("skipped"));
+        let filter = EventFilter {
+            types: vec!["user".to_string()],
+            ..Default::default()
+        };
+        let selection = Selection {
+            window: Window::Tail(3),
+            reverse: true,
+            dedup_progress: false,
+            output: ReadOutput::JsonEvents,
+        };
+        let json =
+            serde_json::to_string_pretty(&select_events(&events, &filter, selection)).unwrap();
+        let printed: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        let uuids: Vec<&str> = printed.iter().filter_map(|e| e["uuid"].as_str()).collect();
+        assert_eq!(uuids, ["d", "c", "b"]);
+    }
 
-    for event in &filtered {
-        print_event(event);
+    #[test]
+    fn filter_tool_keeps_calls_and_their_results() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "user", "message": { "content": "run the tests" } },
+            { "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": {} }
+            ]}},
+            { "type": "assistant", "message": { "content": [
+                { "type": "tool_use", "id": "tu_2", "name": "Read", "input": {} }
+            ]}},
+            { "type": "user", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "tu_2", "content": "file" }
+            ]}},
+            { "type": "user", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "tu_1", "content": "ok" }
+            ]}}
+        ]))
+        .unwrap();
+        let kept = filter_tool(events.iter().collect(), "bash");
+        let kept: Vec<usize> = kept
+            .iter()
+            .map(|k| events.iter().position(|e| std::ptr::eq(e, *k)).unwrap())
+            .collect();
+        assert_eq!(kept, vec![1, 4]);
+        assert!(filter_tool(events.iter().collect(), "Edit").is_empty());
     }
 
-    Ok(())
-}
+    #[test]
+    fn dedup_progress_keeps_last_of_each_run() {
+        let progress = |id: &str, secs: u64| {
+            SessionEvent::ToolProgress(ToolProgressEvent {
+                created_at: None,
+                uuid: None,
+                session_id: None,
+                tool_name: Some("Bash".to_string()),
+                tool_use_id: Some(id.to_string()),
+                parent_tool_use_id: None,
+                elapsed_time_seconds: Some(secs),
+            })
+        };
+        let events = [
+            progress("tu_1", 1),
+            progress("tu_1", 2),
+            progress("tu_1", 3),
+            progress("tu_2", 1),
+            make_summary_event("between"),
+            progress("tu_2", 5),
+            progress("tu_2", 6),
+        ];
+        let elapsed: Vec<Option<u64>> = dedup_progress(events.iter().collect())
+            .into_iter()
+            .map(|e| match e {
+                SessionEvent::ToolProgress(p) => p.elapsed_time_seconds,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(elapsed, vec![Some(3), Some(1), None, Some(6)]);
+    }
 
-async fn cmd_summary(session_id: &str) -> Result<()> {
-    validate_session_id(session_id)?;
-    let api = ApiClient::new().await?;
-    let session = api.get_session(session_id).await?;
+    #[test]
+    fn event_filter_excludes_replayed_user_turns() {
+        let mut replayed = make_user_event("earlier question");
+        if let SessionEvent::User(ref mut u) = replayed {
+            u.is_replay = Some(true);
+        }
+        let filter = EventFilter {
+            exclude_replay: true,
+            ..Default::default()
+        };
+        assert!(!filter.matches(&replayed));
+        assert!(filter.matches(&make_user_event("new question")));
+        assert!(EventFilter::default().matches(&replayed));
+    }
 
-    println!("\n{}\n", "Session Summary".bold());
-    println!(
-        "  {} ({})",
-        session.title.as_deref().unwrap_or("(untitled)").bold(),
-        status_colored(session.session_status.as_deref().unwrap_or("unknown"))
-    );
-    println!();
+    #[test]
+    fn event_filter_combines_conditions() {
+        let filter = EventFilter {
+            conversation_only: true,
+            search: Some(TextQuery {
+                text: "answer".to_string(),
+                fields: Vec::new(),
+            }),
+            ..Default::default()
+        };
+        assert!(filter.matches(&make_assistant_event("the answer")));
+        assert!(!filter.matches(&make_assistant_event("nothing here")));
+        assert!(!filter.matches(&make_summary_event("the answer")));
+    }
 
-    eprintln!("Fetching events...");
-    let events = api.get_events(session_id, 0).await?;
+    #[test]
+    fn assistant_only_keeps_assistant_text() {
+        let filter = EventFilter {
+            assistant_only: true,
+            ..Default::default()
+        };
+        let event = |v: serde_json::Value| serde_json::from_value::<SessionEvent>(v).unwrap();
 
-    let mut type_counts: HashMap<&str, usize> = HashMap::new();
-    for e in &events {
-        *type_counts.entry(e.event_type()).or_default() += 1;
+        assert!(filter.matches(&make_assistant_event("the answer")));
+        assert!(!filter.matches(&make_user_event("the question")));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "type": "assistant",
+            "message": { "content": [
+                { "type": "thinking", "thinking": "hmm" },
+                { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": {} }
+            ] }
+        }))));
     }
 
-    println!("  {}: {}", "Total events".dimmed(), events.len());
-    let mut sorted_types: Vec<_> = type_counts.iter().collect();
-    sorted_types.sort_by(|a, b| b.1.cmp(a.1));
-    for (t, c) in &sorted_types {
-        println!("    {}: {c}", t.dimmed());
+    #[test]
+    fn only_errors_keeps_failure_events() {
+        let filter = EventFilter {
+            only_errors: true,
+            ..Default::default()
+        };
+        let event = |v: serde_json::Value| serde_json::from_value::<SessionEvent>(v).unwrap();
+
+        assert!(filter.matches(&event(
+            serde_json::json!({ "type": "result", "errors": ["boom"] })
+        )));
+        assert!(!filter.matches(&event(
+            serde_json::json!({ "type": "result", "errors": [] })
+        )));
+        assert!(filter.matches(&event(serde_json::json!({
+            "type": "assistant",
+            "message": { "content": [{ "type": "tool_result", "content": "x", "is_error": true }] }
+        }))));
+        assert!(filter.matches(&event(serde_json::json!({
+            "type": "user",
+            "message": { "content": [{ "type": "tool_result", "content": "x", "is_error": true }] }
+        }))));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "type": "user",
+            "message": { "content": [{ "type": "tool_result", "content": "x", "is_error": false }] }
+        }))));
+        assert!(filter.matches(&event(serde_json::json!({
+            "type": "env_manager_log",
+            "data": { "level": "error", "content": "clone failed" }
+        }))));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "type": "env_manager_log",
+            "data": { "level": "warn", "content": "slow" }
+        }))));
+        assert!(!filter.matches(&make_assistant_event("all good")));
     }
-    println!();
 
-    // Tool-use summaries
-    let summaries: Vec<&str> = events
-        .iter()
-        .filter_map(|e| match e {
-            SessionEvent::ToolUseSummary(s) => s.summary.as_deref(),
-            _ => None,
-        })
-        .collect();
+    #[test]
+    fn only_errors_composes_with_type_filter() {
+        let filter = EventFilter {
+            only_errors: true,
+            types: vec!["result".to_string()],
+            ..Default::default()
+        };
+        let log: SessionEvent = serde_json::from_value(serde_json::json!({
+            "type": "env_manager_log",
+            "data": { "level": "error" }
+        }))
+        .unwrap();
+        assert!(!filter.matches(&log));
+    }
 
-    if !summaries.is_empty() {
-        println!("  {} ({}):", "Tool Use Summaries".bold(), summaries.len());
-        for (i, s) in summaries.iter().enumerate() {
-            let prefix = if i == summaries.len() - 1 {
-                "  \u{2514}\u{2500}"
-            } else {
-                "  \u{251c}\u{2500}"
-            };
-            println!("  {prefix} {s}");
-        }
+    // ── query_events ────────────────────────────────────────────────
+
+    fn events_with_uuids(uuids: &[&str]) -> Vec<SessionEvent> {
+        uuids
+            .iter()
+            .map(|id| {
+                serde_json::from_value(serde_json::json!({
+                    "type": "user",
+                    "uuid": id,
+                    "message": { "content": "hi" }
+                }))
+                .unwrap()
+            })
+            .collect()
     }
 
-    // User messages
-    let user_messages: Vec<&str> = events
-        .iter()
-        .filter_map(|e| match e {
-            SessionEvent::User(u) => u.message.content.as_text(),
-            _ => None,
-        })
-        .collect();
+    fn uuids(events: &[SessionEvent]) -> Vec<&str> {
+        events.iter().filter_map(|e| e.uuid()).collect()
+    }
 
-    if !user_messages.is_empty() {
-        println!("\n  {} ({}):", "User Messages".bold(), user_messages.len());
-        for (i, msg) in user_messages.iter().enumerate() {
-            let preview: String = msg.chars().take(120).collect();
-            let suffix = if msg.len() > 120 { "..." } else { "" };
-            let prefix = if i == user_messages.len() - 1 {
-                "  \u{2514}\u{2500}"
-            } else {
-                "  \u{251c}\u{2500}"
-            };
-            println!("  {prefix} {preview}{suffix}");
-        }
+    #[tokio::test]
+    async fn file_source_streams_queried_events() {
+        let source = Source::File(Box::new(SessionExport {
+            session: Session::default(),
+            events: events_with_uuids(&["a", "b", "c"]),
+            total_events: None,
+            exported_at: None,
+        }));
+        let query = EventQuery {
+            after_id: Some("a".to_string()),
+            ..Default::default()
+        };
+        let mut seen = Vec::new();
+        let count = source
+            .for_each_event("s", &query, |event| {
+                seen.push(event.uuid().unwrap().to_string());
+                Ok(ControlFlow::Continue(()))
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(seen, ["b", "c"]);
     }
 
-    println!();
-    Ok(())
-}
+    #[test]
+    fn query_events_applies_cursor_bounds_and_cap() {
+        let events = events_with_uuids(&["a", "b", "c", "d", "e"]);
+        let query = EventQuery {
+            after_id: Some("a".to_string()),
+            before_id: Some("e".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            uuids(query_events(&events, &query).unwrap()),
+            vec!["b", "c", "d"]
+        );
+        let capped = EventQuery {
+            max_events: 2,
+            ..query
+        };
+        assert_eq!(
+            uuids(query_events(&events, &capped).unwrap()),
+            vec!["b", "c"]
+        );
+        let unknown_before = EventQuery {
+            before_id: Some("zz".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query_events(&events, &unknown_before).unwrap().len(), 5);
+    }
 
-async fn cmd_loglines(session_id: &str) -> Result<()> {
-    validate_session_id(session_id)?;
-    let api = ApiClient::new().await?;
+    #[test]
+    fn query_events_rejects_unknown_after_id() {
+        let events = events_with_uuids(&["a"]);
+        let query = EventQuery {
+            after_id: Some("missing".to_string()),
+            ..Default::default()
+        };
+        let err = query_events(&events, &query).unwrap_err();
+        assert_eq!(err.to_string(), "No event with ID missing");
+    }
 
-    eprintln!("Fetching session loglines...");
-    let loglines = api.get_loglines(session_id).await?;
+    // ── run_per_session ─────────────────────────────────────────────
 
-    println!(
-        "\n{} ({} loglines)\n",
-        "Session Loglines".bold(),
-        loglines.len()
-    );
+    #[tokio::test]
+    async fn run_per_session_reports_a_panicking_task_as_an_error() {
+        let ids = ["session_01a", "session_01b", "session_01c"].map(String::from);
+        let results = run_per_session(ids.to_vec(), 2, false, |id| async move {
+            assert_ne!(id, "session_01b", "stub failure");
+            anyhow::ensure!(id != "session_01c", "network down");
+            Ok(id.len())
+        })
+        .await;
 
-    for log in &loglines {
-        print_logline(log);
+        assert_eq!(results[0].as_ref().unwrap(), &11);
+        let panicked = results[1].as_ref().unwrap_err().to_string();
+        assert!(panicked.starts_with("search task failed"), "{panicked}");
+        assert_eq!(results[2].as_ref().unwrap_err().to_string(), "network down");
     }
 
-    Ok(())
-}
-
-async fn cmd_export(session_id: &str, output: &str) -> Result<()> {
-    validate_session_id(session_id)?;
+    // ── format_match_counts ─────────────────────────────────────────
 
-    // Validate output path
-    let path = std::path::Path::new(output);
-    if let Some(parent) = path.parent()
-        && !parent.as_os_str().is_empty()
-        && !parent.exists()
-    {
-        bail!("Output directory does not exist: {}", parent.display());
+    #[test]
+    fn match_counts_sorted_descending() {
+        let lines = format_match_counts(vec![
+            ("session_01b", 2),
+            ("session_01c", 0),
+            ("session_01a", 2),
+            ("session_01d", 7),
+        ]);
+        assert_eq!(
+            lines,
+            vec![
+                "session_01d: 7 matches",
+                "session_01a: 2 matches",
+                "session_01b: 2 matches",
+                "session_01c: 0 matches",
+            ]
+        );
     }
 
-    let api = ApiClient::new().await?;
+    // ── find_in_path ────────────────────────────────────────────────
 
-    eprintln!("Fetching session metadata...");
-    let session = api.get_session(session_id).await?;
+    #[test]
+    fn find_in_path_locates_file() {
+        let dir = std::env::temp_dir().join("cta-test-find-in-path");
+        std::fs::create_dir_all(&dir).unwrap();
+        let name = if cfg!(windows) {
+            "fakeclaude.exe"
+        } else {
+            "fakeclaude"
+        };
+        std::fs::write(dir.join(name), "").unwrap();
 
-    eprintln!("Fetching all events...");
-    let events = api.get_events(session_id, 0).await?;
+        let path_var = std::env::join_paths([std::path::Path::new("/nonexistent"), &dir]).unwrap();
+        assert_eq!(find_in_path("fakeclaude", &path_var), Some(dir.join(name)));
+        assert!(find_in_path("definitely-not-here", &path_var).is_none());
 
-    let export = serde_json::json!({
-        "session": session,
-        "events": events,
-        "exported_at": Utc::now().to_rfc3339(),
-        "total_events": events.len(),
-    });
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 
-    std::fs::write(output, serde_json::to_string_pretty(&export)?)
-        .with_context(|| format!("Failed to write export to {output}"))?;
-    println!(
-        "\nExported {} events to {}\n",
-        events.len().to_string().cyan(),
-        output.green()
-    );
+    // ── sort_sessions ───────────────────────────────────────────────
 
-    Ok(())
-}
+    fn make_session(id: &str, title: Option<&str>, created: Option<&str>) -> Session {
+        Session {
+            id: id.to_string(),
+            title: title.map(String::from),
+            session_status: None,
+            session_type: None,
+            created_at: created.map(String::from),
+            updated_at: None,
+            environment_id: None,
+            session_context: None,
+            metadata: None,
+            active_mount_paths: None,
+        }
+    }
 
-// ── Main ─────────────────────────────────────────────────────────────
+    fn ids(sessions: &[&Session]) -> Vec<String> {
+        sessions.iter().map(|s| s.id.clone()).collect()
+    }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    // ── list --columns ─────────────────────────────────────────────
 
-    match cli.command {
-        Commands::List {
-            limit,
-            status,
-            after,
-            before,
-        } => cmd_list(limit, status, after, before).await,
-        Commands::Show { session_id } => cmd_show(&session_id).await,
-        Commands::Read {
-            session_id,
-            conversation_only,
-            r#type,
-            max_events,
-            search,
-        } => cmd_read(&session_id, conversation_only, r#type, max_events, search).await,
-        Commands::Summary { session_id } => cmd_summary(&session_id).await,
-        Commands::Loglines { session_id } => cmd_loglines(&session_id).await,
-        Commands::Export { session_id, output } => cmd_export(&session_id, &output).await,
+    #[test]
+    fn column_lines_align_to_widest_cell() {
+        let mut long = make_session("session_long", Some("Fix it"), None);
+        long.session_status = Some("completed".to_string());
+        long.session_context = Some(SessionContext {
+            model: Some("claude-opus".to_string()),
+            ..Default::default()
+        });
+        let short = make_session("s2", None, None);
+        let lines = column_lines(
+            &[&long, &short],
+            &[ListColumn::Id, ListColumn::Status, ListColumn::Model],
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "ID            STATUS     MODEL",
+                "session_long  completed  claude-opus",
+                "s2            -          -",
+            ]
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn column_lines_format_timestamps_and_repo() {
+        let mut session = make_session("s1", None, Some("2025-01-01T00:00:00Z"));
+        session.session_context = Some(SessionContext {
+            sources: Some(vec![SessionSource {
+                source_type: Some("git".to_string()),
+                url: Some("https://github.com/o/r".to_string()),
+                revision: None,
+            }]),
+            ..Default::default()
+        });
+        let lines = column_lines(&[&session], &[ListColumn::Created, ListColumn::Repo]);
+        assert_eq!(lines[1], "2025-01-01 00:00:00 UTC  https://github.com/o/r");
+    }
 
-    // ── parse_date_filter ───────────────────────────────────────────
+    #[test]
+    fn sort_created_newest_first_missing_last() {
+        let a = make_session("a", None, Some("2025-01-01T00:00:00Z"));
+        let b = make_session("b", None, None);
+        let c = make_session("c", None, Some("2025-03-01T00:00:00Z"));
+        let mut v = vec![&a, &b, &c];
+        sort_sessions(&mut v, SortField::Created, false);
+        assert_eq!(ids(&v), ["c", "a", "b"]);
+
+        sort_sessions(&mut v, SortField::Created, true);
+        assert_eq!(ids(&v), ["a", "c", "b"]);
+    }
 
     #[test]
-    fn parse_date_filter_iso8601() {
-        let dt = parse_date_filter("2025-06-15T14:30:00Z").unwrap();
-        assert_eq!(dt.format("%Y-%m-%d").to_string(), "2025-06-15");
+    fn sort_title_case_insensitive_missing_last() {
+        let a = make_session("a", Some("beta"), None);
+        let b = make_session("b", None, None);
+        let c = make_session("c", Some("Alpha"), None);
+        let mut v = vec![&a, &b, &c];
+        sort_sessions(&mut v, SortField::Title, false);
+        assert_eq!(ids(&v), ["c", "a", "b"]);
+
+        sort_sessions(&mut v, SortField::Title, true);
+        assert_eq!(ids(&v), ["a", "c", "b"]);
     }
 
     #[test]
-    fn parse_date_filter_date_only() {
-        let dt = parse_date_filter("2025-06-15").unwrap();
+    fn sort_by_activity_prefers_updated_then_created() {
+        let with_updated = |id: &str, updated: Option<&str>, created: Option<&str>| Session {
+            updated_at: updated.map(String::from),
+            ..make_session(id, None, created)
+        };
+        let old = with_updated("old", Some("2025-01-01T00:00:00Z"), None);
+        let new = with_updated("new", Some("2025-03-01T00:00:00Z"), None);
+        let tie_young = with_updated(
+            "tie_young",
+            Some("2025-02-01T00:00:00Z"),
+            Some("2025-01-20T00:00:00Z"),
+        );
+        let tie_old = with_updated(
+            "tie_old",
+            Some("2025-02-01T00:00:00Z"),
+            Some("2025-01-10T00:00:00Z"),
+        );
+        let created_only = with_updated("created_only", None, Some("2025-06-01T00:00:00Z"));
+        let bare = with_updated("bare", None, None);
+        let mut v = vec![&bare, &old, &created_only, &tie_old, &new, &tie_young];
+        sort_by_activity(&mut v);
         assert_eq!(
-            dt.format("%Y-%m-%d %H:%M:%S").to_string(),
-            "2025-06-15 00:00:00"
+            ids(&v),
+            ["new", "tie_young", "tie_old", "old", "created_only", "bare"]
         );
     }
 
     #[test]
-    fn parse_date_filter_invalid() {
-        let err = parse_date_filter("not-a-date").unwrap_err();
-        assert!(err.to_string().contains("Invalid date format"));
+    fn select_sessions_limit_zero_keeps_all() {
+        let sessions: Vec<Session> = (0..25)
+            .map(|i| make_session(&format!("s{i}"), None, None))
+            .collect();
+        let filter = SessionFilter::default();
+        assert_eq!(
+            select_sessions(&sessions, 0, &filter, None, false).len(),
+            25
+        );
+        assert_eq!(
+            select_sessions(&sessions, 20, &filter, None, false).len(),
+            20
+        );
     }
 
     // ── event_contains_text ─────────────────────────────────────────
@@ -497,36 +4238,56 @@ mod tests {
     #[test]
     fn search_user_event_matches() {
         let event = make_user_event("Hello World");
-        assert!(event_contains_text(&event, "hello"));
-        assert!(event_contains_text(&event, "WORLD"));
-        assert!(!event_contains_text(&event, "missing"));
+        assert!(event_contains_text(&event, "hello", &[]));
+        assert!(event_contains_text(&event, "WORLD", &[]));
+        assert!(!event_contains_text(&event, "missing", &[]));
+    }
+
+    #[test]
+    fn search_user_blocks_matches_text_and_tool_results() {
+        let event: SessionEvent = serde_json::from_value(serde_json::json!({
+            "type": "user",
+            "message": { "content": [
+                { "type": "text", "text": "Look at this Screenshot" },
+                { "type": "image", "source": { "data": "aGVsbG8=" } },
+                { "type": "tool_result", "tool_use_id": "tu_1", "content": "build FAILED" }
+            ] }
+        }))
+        .unwrap();
+        assert!(event_contains_text(&event, "screenshot", &[]));
+        assert!(event_contains_text(&event, "build failed", &[]));
+        assert!(!event_contains_text(&event, "aGVsbG8", &[]));
     }
 
     #[test]
     fn search_assistant_event_matches() {
         let event = make_assistant_event("Here is the answer");
-        assert!(event_contains_text(&event, "answer"));
-        assert!(!event_contains_text(&event, "question"));
+        assert!(event_contains_text(&event, "answer", &[]));
+        assert!(!event_contains_text(&event, "question", &[]));
     }
 
     #[test]
     fn search_tool_summary_matches() {
         let event = make_summary_event("Read 3 files and edited main.rs");
-        assert!(event_contains_text(&event, "main.rs"));
-        assert!(!event_contains_text(&event, "cargo.toml"));
+        assert!(event_contains_text(&event, "main.rs", &[]));
+        assert!(!event_contains_text(&event, "cargo.toml", &[]));
     }
 
     #[test]
     fn search_case_insensitive() {
         let event = make_user_event("CamelCase mixed TEXT");
-        assert!(event_contains_text(&event, "camelcase"));
-        assert!(event_contains_text(&event, "MIXED"));
-        assert!(event_contains_text(&event, "text"));
+        assert!(event_contains_text(&event, "camelcase", &[]));
+        assert!(event_contains_text(&event, "MIXED", &[]));
+        assert!(event_contains_text(&event, "text", &[]));
     }
 
     #[test]
     fn search_unknown_event_returns_false() {
-        assert!(!event_contains_text(&SessionEvent::Unknown, "anything"));
+        assert!(!event_contains_text(
+            &SessionEvent::Unknown,
+            "anything",
+            &[]
+        ));
     }
 
     #[test]
@@ -544,9 +4305,116 @@ mod tests {
                 })],
             },
         });
-        assert!(event_contains_text(&event, "Bash"));
-        assert!(event_contains_text(&event, "cargo test"));
-        assert!(!event_contains_text(&event, "npm"));
+        assert!(event_contains_text(&event, "Bash", &[]));
+        assert!(event_contains_text(&event, "cargo test", &[]));
+        assert!(!event_contains_text(&event, "npm", &[]));
+    }
+
+    #[test]
+    fn search_fields_restrict_where_text_is_looked_for() {
+        use SearchField::*;
+        let tool_use: SessionEvent = serde_json::from_value(serde_json::json!({
+            "type": "assistant",
+            "message": { "content": [
+                { "type": "thinking", "thinking": "maybe run cargo" },
+                { "type": "tool_use", "name": "Bash", "input": { "command": "cargo test" } }
+            ]}
+        }))
+        .unwrap();
+        assert!(event_contains_text(&tool_use, "cargo", &[ToolInput]));
+        assert!(event_contains_text(&tool_use, "cargo", &[Thinking]));
+        assert!(!event_contains_text(
+            &tool_use,
+            "cargo",
+            &[ToolName, AssistantText]
+        ));
+        assert!(event_contains_text(&tool_use, "bash", &[ToolName]));
+
+        let user = make_user_event("fix the build");
+        assert!(event_contains_text(&user, "build", &[User]));
+        assert!(!event_contains_text(
+            &user,
+            "build",
+            &[AssistantText, ToolResult]
+        ));
+
+        let summary = make_summary_event("Ran the build");
+        assert!(event_contains_text(&summary, "build", &[]));
+        assert!(!event_contains_text(&summary, "build", &[User, ToolResult]));
+    }
+
+    #[test]
+    fn type_filter_empty_matches_everything() {
+        assert!(event_matches_types(&make_user_event("hi"), &[]));
+        assert!(event_matches_types(&SessionEvent::Unknown, &[]));
+    }
+
+    #[test]
+    fn type_filter_single_value() {
+        let types = vec!["user".to_string()];
+        assert!(event_matches_types(&make_user_event("hi"), &types));
+        assert!(!event_matches_types(&make_assistant_event("hi"), &types));
+    }
+
+    #[test]
+    fn type_filter_multiple_values() {
+        let types = vec!["user".to_string(), "assistant".to_string()];
+        assert!(event_matches_types(&make_user_event("hi"), &types));
+        assert!(event_matches_types(&make_assistant_event("hi"), &types));
+        assert!(!event_matches_types(&make_summary_event("hi"), &types));
+    }
+
+    fn make_env_log(level: Option<&str>) -> SessionEvent {
+        SessionEvent::EnvManagerLog(EnvManagerLogEvent {
+            created_at: None,
+            uuid: None,
+            data: Some(EnvManagerLogData {
+                category: None,
+                content: Some("log".to_string()),
+                level: level.map(String::from),
+                timestamp: None,
+                extra: None,
+            }),
+        })
+    }
+
+    #[test]
+    fn min_level_filters_env_logs() {
+        let min = Some(LogLevel::Warn);
+        assert!(!event_meets_level(&make_env_log(Some("debug")), min));
+        assert!(!event_meets_level(&make_env_log(Some("info")), min));
+        assert!(!event_meets_level(&make_env_log(None), min));
+        assert!(event_meets_level(&make_env_log(Some("warn")), min));
+        assert!(event_meets_level(&make_env_log(Some("ERROR")), min));
+    }
+
+    #[test]
+    fn min_level_ignores_other_events() {
+        let min = Some(LogLevel::Error);
+        assert!(event_meets_level(&make_user_event("hi"), min));
+        assert!(event_meets_level(&make_env_log(Some("debug")), None));
+    }
+
+    #[test]
+    fn category_filters_env_logs_only() {
+        let mut setup = make_env_log(Some("info"));
+        if let SessionEvent::EnvManagerLog(e) = &mut setup {
+            e.data.as_mut().unwrap().category = Some("setup".to_string());
+        }
+        assert!(event_in_category(&setup, Some("Setup")));
+        assert!(!event_in_category(&setup, Some("network")));
+        assert!(!event_in_category(
+            &make_env_log(Some("info")),
+            Some("setup")
+        ));
+        assert!(event_in_category(&make_user_event("hi"), Some("setup")));
+
+        let filter = EventFilter {
+            category: Some("setup".to_string()),
+            min_level: Some(LogLevel::Warn),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&setup));
     }
 
     #[test]
@@ -562,7 +4430,74 @@ mod tests {
                 extra: None,
             }),
         });
-        assert!(event_contains_text(&event, "dependencies"));
-        assert!(!event_contains_text(&event, "compiling"));
+        assert!(event_contains_text(&event, "dependencies", &[]));
+        assert!(!event_contains_text(&event, "compiling", &[]));
+    }
+
+    // ── export_value ────────────────────────────────────────────────
+
+    #[test]
+    fn flattened_export_keeps_every_event_and_total() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "assistant", "uuid": "a", "message": { "content": [
+                { "type": "tool_use", "id": "tu_1", "name": "Bash", "input": {} }
+            ] } },
+            { "type": "user", "uuid": "b", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "tu_1", "content": "ok" }
+            ] } }
+        ]))
+        .unwrap();
+        let export = SessionExport {
+            session: Session::default(),
+            events,
+            total_events: Some(2),
+            exported_at: None,
+        };
+
+        let value = export_value(&export, true).unwrap();
+        assert_eq!(value["total_events"], 2);
+        let events = value["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0]["message"]["content"][0]["tool_result"]["content"],
+            "ok"
+        );
+        assert_eq!(events[1]["uuid"], "b");
+        assert_eq!(events[1]["message"]["content"], serde_json::json!([]));
+
+        let plain = export_value(&export, false).unwrap();
+        assert_eq!(plain, serde_json::to_value(&export).unwrap());
+    }
+
+    // ── exit_code_for ───────────────────────────────────────────────
+
+    #[test]
+    fn api_errors_map_to_distinct_exit_codes() {
+        let code = |status: u16| {
+            let status = reqwest::StatusCode::from_u16(status).unwrap();
+            let err = anyhow::Error::new(ApiError::from_status("Failed", status, ""))
+                .context("Failed to fetch session");
+            exit_code_for(&err)
+        };
+        assert_eq!(code(401), exit_code::AUTH);
+        assert_eq!(code(404), exit_code::NOT_FOUND);
+        assert_eq!(code(429), exit_code::RATE_LIMITED);
+        assert_eq!(code(502), exit_code::SERVER);
+        assert_eq!(code(400), exit_code::FAILURE);
+        assert_eq!(
+            exit_code_for(&anyhow::anyhow!("bad date")),
+            exit_code::FAILURE
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_failures_exit_with_network_code() {
+        let err = reqwest::Client::new()
+            .get("http://127.0.0.1:1/")
+            .send()
+            .await
+            .unwrap_err();
+        let err = anyhow::Error::new(err).context("Failed to list sessions");
+        assert_eq!(exit_code_for(&err), exit_code::NETWORK);
     }
 }
@@ -0,0 +1,312 @@
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::export::ToolUsageRow;
+use crate::types::SessionEvent;
+
+// ── Grouping ─────────────────────────────────────────────────────────
+
+/// Dimension used to bucket tool calls in `stats --group-by`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// Calendar hour (UTC) of the call.
+    Hour,
+    /// Ten-minute window (UTC) of the call.
+    #[value(name = "10min")]
+    TenMin,
+    /// Tool name.
+    Tool,
+    /// Model active when the call was made.
+    Model,
+}
+
+impl GroupBy {
+    fn is_time(self) -> bool {
+        matches!(self, Self::Hour | Self::TenMin)
+    }
+
+    fn key(self, row: &ToolUsageRow) -> String {
+        match self {
+            Self::Hour | Self::TenMin => {
+                let Ok(dt) = row.timestamp.parse::<DateTime<Utc>>() else {
+                    return "(no timestamp)".to_string();
+                };
+                let minute = if self == Self::Hour {
+                    0
+                } else {
+                    dt.minute() / 10 * 10
+                };
+                format!("{} {:02}:{minute:02}", dt.format("%Y-%m-%d"), dt.hour())
+            }
+            Self::Tool => row.tool_name.clone(),
+            Self::Model => row.model.clone().unwrap_or_else(|| "(unknown)".to_string()),
+        }
+    }
+}
+
+/// Call counts and durations for a set of tool calls.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ToolStats {
+    pub calls: usize,
+    pub errors: usize,
+    /// Sum of each call's longest `tool_progress` report.
+    pub elapsed_seconds: u64,
+}
+
+impl ToolStats {
+    fn add(&mut self, row: &ToolUsageRow) {
+        self.calls += 1;
+        self.errors += usize::from(row.is_error == Some(true));
+        self.elapsed_seconds += row.elapsed_seconds.unwrap_or(0);
+    }
+}
+
+/// Tool calls sharing one group key, with a per-tool breakdown.
+#[derive(Debug, Clone)]
+pub struct Bucket {
+    pub key: String,
+    pub total: ToolStats,
+    pub by_tool: BTreeMap<String, ToolStats>,
+}
+
+impl Bucket {
+    fn new(key: String) -> Self {
+        Self {
+            key,
+            total: ToolStats::default(),
+            by_tool: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, row: &ToolUsageRow) {
+        self.total.add(row);
+        self.by_tool
+            .entry(row.tool_name.clone())
+            .or_default()
+            .add(row);
+    }
+}
+
+/// Aggregates all rows into a single bucket.
+pub fn overall(rows: &[ToolUsageRow]) -> Bucket {
+    let mut bucket = Bucket::new("all".to_string());
+    for row in rows {
+        bucket.add(row);
+    }
+    bucket
+}
+
+/// Buckets rows by `group_by`. Time buckets are chronological; tool and
+/// model buckets are ordered by call count, busiest first.
+pub fn group_tool_usage(rows: &[ToolUsageRow], group_by: GroupBy) -> Vec<Bucket> {
+    let mut buckets: HashMap<String, Bucket> = HashMap::new();
+    for row in rows {
+        let key = group_by.key(row);
+        buckets
+            .entry(key.clone())
+            .or_insert_with(|| Bucket::new(key))
+            .add(row);
+    }
+
+    let mut buckets: Vec<Bucket> = buckets.into_values().collect();
+    if group_by.is_time() {
+        buckets.sort_by(|a, b| a.key.cmp(&b.key));
+    } else {
+        buckets.sort_by(|a, b| b.total.calls.cmp(&a.total.calls).then(a.key.cmp(&b.key)));
+    }
+    buckets
+}
+
+// ── Slowest calls ────────────────────────────────────────────────────
+
+/// The `k` tool calls with the longest `tool_progress` time, slowest first;
+/// ties go to the earlier call. Calls without progress reports are skipped.
+pub fn slowest_calls(rows: &[ToolUsageRow], k: usize) -> Vec<&ToolUsageRow> {
+    let mut timed: Vec<&ToolUsageRow> = rows
+        .iter()
+        .filter(|r| r.elapsed_seconds.is_some())
+        .collect();
+    timed.sort_by(|a, b| {
+        b.elapsed_seconds
+            .cmp(&a.elapsed_seconds)
+            .then_with(|| a.timestamp.cmp(&b.timestamp))
+    });
+    timed.truncate(k);
+    timed
+}
+
+// ── Timing ───────────────────────────────────────────────────────────
+
+/// How long a session ran end to end, and how much of that turns spent working.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTiming {
+    /// Span from the earliest to the latest event timestamp; `None` if fewer
+    /// than two events have a parseable timestamp.
+    pub wall_clock_ms: Option<u64>,
+    /// Sum of `duration_ms` over result events (one per turn).
+    pub turn_ms: u64,
+    /// Sum of `duration_api_ms` over result events.
+    pub api_ms: u64,
+}
+
+pub fn session_timing(events: &[SessionEvent]) -> SessionTiming {
+    let mut timing = SessionTiming::default();
+    let mut first: Option<DateTime<Utc>> = None;
+    let mut last: Option<DateTime<Utc>> = None;
+    let mut stamped = 0;
+    for event in events {
+        if let SessionEvent::Result(r) = event {
+            timing.turn_ms += r.duration_ms.unwrap_or(0);
+            timing.api_ms += r.duration_api_ms.unwrap_or(0);
+        }
+        let Some(at) = event
+            .created_at()
+            .and_then(|t| t.parse::<DateTime<Utc>>().ok())
+        else {
+            continue;
+        };
+        stamped += 1;
+        first = Some(first.map_or(at, |f| f.min(at)));
+        last = Some(last.map_or(at, |l| l.max(at)));
+    }
+    if stamped >= 2
+        && let (Some(first), Some(last)) = (first, last)
+    {
+        timing.wall_clock_ms = Some((last - first).num_milliseconds().unsigned_abs());
+    }
+    timing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(timestamp: &str, tool: &str, model: Option<&str>, secs: Option<u64>) -> ToolUsageRow {
+        ToolUsageRow {
+            timestamp: timestamp.to_string(),
+            tool_name: tool.to_string(),
+            tool_use_id: String::new(),
+            input_bytes: 0,
+            is_error: None,
+            elapsed_seconds: secs,
+            model: model.map(str::to_string),
+        }
+    }
+
+    fn keys(buckets: &[Bucket]) -> Vec<&str> {
+        buckets.iter().map(|b| b.key.as_str()).collect()
+    }
+
+    // ── session_timing ──────────────────────────────────────────────
+
+    #[test]
+    fn session_timing_spans_events_and_sums_result_durations() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "user", "created_at": "2025-01-01T10:00:05Z", "message": { "content": "go" } },
+            { "type": "system", "created_at": "2025-01-01T10:00:00Z" },
+            { "type": "result", "created_at": "2025-01-01T10:10:00Z",
+              "duration_ms": 60000, "duration_api_ms": 45000 },
+            { "type": "result", "duration_ms": 30000, "duration_api_ms": 20000 }
+        ]))
+        .unwrap();
+        let timing = session_timing(&events);
+        assert_eq!(timing.wall_clock_ms, Some(600_000));
+        assert_eq!(timing.turn_ms, 90_000);
+        assert_eq!(timing.api_ms, 65_000);
+    }
+
+    #[test]
+    fn session_timing_needs_two_timestamps_for_wall_clock() {
+        let events: Vec<SessionEvent> = serde_json::from_value(serde_json::json!([
+            { "type": "system", "created_at": "2025-01-01T10:00:00Z" },
+            { "type": "system", "created_at": "not a time" }
+        ]))
+        .unwrap();
+        assert_eq!(session_timing(&events).wall_clock_ms, None);
+    }
+
+    // ── slowest_calls ───────────────────────────────────────────────
+
+    #[test]
+    fn slowest_calls_ranks_by_elapsed_and_skips_untimed() {
+        let rows = vec![
+            row("2025-01-01T10:00:00Z", "Read", None, Some(2)),
+            row("2025-01-01T10:01:00Z", "Bash", None, Some(90)),
+            row("2025-01-01T10:02:00Z", "Grep", None, None),
+            row("2025-01-01T10:03:00Z", "Edit", None, Some(2)),
+            row("2025-01-01T10:04:00Z", "Bash", None, Some(30)),
+        ];
+        let names = |k| -> Vec<&str> {
+            slowest_calls(&rows, k)
+                .iter()
+                .map(|r| r.tool_name.as_str())
+                .collect()
+        };
+        assert_eq!(names(10), ["Bash", "Bash", "Read", "Edit"]);
+        assert_eq!(names(2), ["Bash", "Bash"]);
+        assert!(names(0).is_empty());
+    }
+
+    // ── group_tool_usage ────────────────────────────────────────────
+
+    #[test]
+    fn group_by_ten_minutes_bins_and_sorts_chronologically() {
+        let rows = vec![
+            row("2025-01-01T10:25:00Z", "Bash", None, Some(3)),
+            row("2025-01-01T10:01:00Z", "Bash", None, Some(2)),
+            row("2025-01-01T10:09:59Z", "Read", None, None),
+        ];
+        let buckets = group_tool_usage(&rows, GroupBy::TenMin);
+        assert_eq!(keys(&buckets), vec!["2025-01-01 10:00", "2025-01-01 10:20"]);
+        assert_eq!(buckets[0].total.calls, 2);
+        assert_eq!(buckets[0].total.elapsed_seconds, 2);
+        assert_eq!(buckets[0].by_tool["Read"].calls, 1);
+    }
+
+    #[test]
+    fn group_by_hour_handles_missing_timestamps() {
+        let rows = vec![
+            row("2025-01-01T10:59:00Z", "Bash", None, None),
+            row("2025-01-01T11:00:00Z", "Bash", None, None),
+            row("", "Edit", None, None),
+        ];
+        let buckets = group_tool_usage(&rows, GroupBy::Hour);
+        assert_eq!(
+            keys(&buckets),
+            vec!["(no timestamp)", "2025-01-01 10:00", "2025-01-01 11:00"]
+        );
+    }
+
+    #[test]
+    fn group_by_tool_orders_by_calls() {
+        let rows = vec![
+            row("", "Read", None, None),
+            row("", "Bash", None, Some(4)),
+            row("", "Bash", None, Some(6)),
+        ];
+        let buckets = group_tool_usage(&rows, GroupBy::Tool);
+        assert_eq!(keys(&buckets), vec!["Bash", "Read"]);
+        assert_eq!(buckets[0].total.elapsed_seconds, 10);
+    }
+
+    #[test]
+    fn group_by_model_labels_unknown() {
+        let rows = vec![
+            row("", "Bash", Some("opus"), None),
+            row("", "Bash", None, None),
+        ];
+        let buckets = group_tool_usage(&rows, GroupBy::Model);
+        assert_eq!(keys(&buckets), vec!["(unknown)", "opus"]);
+    }
+
+    #[test]
+    fn overall_counts_errors() {
+        let mut failed = row("", "Bash", None, None);
+        failed.is_error = Some(true);
+        let rows = vec![failed, row("", "Bash", None, None)];
+        let bucket = overall(&rows);
+        assert_eq!(bucket.total.calls, 2);
+        assert_eq!(bucket.total.errors, 1);
+    }
+}
@@ -0,0 +1,216 @@
+use colored::{Color, ColoredString, Colorize};
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// Environment variable consulted when `--theme` isn't given.
+pub const THEME_ENV: &str = "CTA_THEME";
+
+/// Color palette for terminal output.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ThemeName {
+    /// Colors for dark terminal backgrounds.
+    #[default]
+    Dark,
+    /// Avoids dim, cyan, and yellow text, which wash out on light backgrounds.
+    Light,
+    /// No colors or styles at all.
+    Mono,
+}
+
+impl ThemeName {
+    /// Parses `CTA_THEME` (case-insensitive); unset or unknown values give `None`.
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var(THEME_ENV).ok()?;
+        <Self as clap::ValueEnum>::from_str(value.trim(), true).ok()
+    }
+}
+
+/// When to emit ANSI colors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorMode {
+    /// Color only when stdout is a terminal, honoring `NO_COLOR` and `CLICOLOR_FORCE`.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// The `colored` override this mode needs; `None` leaves the decision to
+    /// `colored`, which checks the environment and whether stdout is a terminal.
+    fn forced(self) -> Option<bool> {
+        match self {
+            Self::Auto => None,
+            Self::Always => Some(true),
+            Self::Never => Some(false),
+        }
+    }
+
+    /// Whether a stream should get colors, by the rules `colored` applies to
+    /// stdout: `CLICOLOR_FORCE` wins, then `NO_COLOR`, then `CLICOLOR=0`, then
+    /// whether the stream is a terminal. `env` looks up a variable.
+    fn colors(self, env: impl Fn(&str) -> Option<String>, is_terminal: bool) -> bool {
+        let set = |name| env(name).map(|value| value != "0");
+        self.forced().unwrap_or_else(|| {
+            if set("CLICOLOR_FORCE") == Some(true) {
+                true
+            } else if set("NO_COLOR").is_some() {
+                false
+            } else {
+                set("CLICOLOR").unwrap_or(true) && is_terminal
+            }
+        })
+    }
+}
+
+/// Applies `mode` process-wide. Call before [`set_theme`], so that `mono`
+/// still wins over `--color always`.
+pub fn set_color_mode(mode: ColorMode) {
+    if let Some(force) = mode.forced() {
+        colored::control::set_override(force);
+    }
+}
+
+/// Whether log lines on stderr should carry ANSI colors: `mode` decides as it
+/// does for stdout, and the `mono` theme turns them off.
+pub fn stderr_colors(mode: ColorMode) -> bool {
+    current_theme() != ThemeName::Mono
+        && mode.colors(
+            |name| std::env::var(name).ok(),
+            std::io::stderr().is_terminal(),
+        )
+}
+
+/// What a piece of output means; the active theme decides how it looks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Timestamps, IDs, labels, and other secondary text.
+    Muted,
+    /// Names worth spotting: models, tools, counts, session IDs.
+    Accent,
+    /// Assistant output and completed sessions.
+    Info,
+    /// System events.
+    Highlight,
+    Success,
+    Warning,
+    Error,
+}
+
+/// How a theme renders one role.
+enum Ink {
+    Dim,
+    Color(Color),
+    Plain,
+}
+
+impl ThemeName {
+    fn ink(self, role: Role) -> Ink {
+        match (self, role) {
+            (Self::Mono, _) => Ink::Plain,
+            (Self::Dark, Role::Muted) => Ink::Dim,
+            (Self::Light, Role::Muted) => Ink::Color(Color::BrightBlack),
+            (Self::Dark, Role::Accent) => Ink::Color(Color::Cyan),
+            (Self::Light, Role::Accent) => Ink::Color(Color::Blue),
+            (_, Role::Info) => Ink::Color(Color::Blue),
+            (_, Role::Highlight) => Ink::Color(Color::Magenta),
+            (_, Role::Success) => Ink::Color(Color::Green),
+            (Self::Dark, Role::Warning) => Ink::Color(Color::Yellow),
+            (Self::Light, Role::Warning) => Ink::Color(Color::Magenta),
+            (_, Role::Error) => Ink::Color(Color::Red),
+        }
+    }
+}
+
+static THEME: OnceLock<ThemeName> = OnceLock::new();
+
+/// Selects the theme for the rest of the process; only the first call has an
+/// effect. `Mono` also turns off `colored` output entirely.
+pub fn set_theme(name: ThemeName) {
+    if THEME.set(name).is_ok() && name == ThemeName::Mono {
+        colored::control::set_override(false);
+    }
+}
+
+/// The active theme; `Dark` unless [`set_theme`] chose another.
+pub fn current_theme() -> ThemeName {
+    THEME.get().copied().unwrap_or_default()
+}
+
+/// Styles text by [`Role`] through the active theme.
+pub trait Themed {
+    fn themed(self, role: Role) -> ColoredString;
+}
+
+impl<T: Colorize> Themed for T {
+    fn themed(self, role: Role) -> ColoredString {
+        match current_theme().ink(role) {
+            Ink::Dim => self.dimmed(),
+            Ink::Color(color) => self.color(color),
+            Ink::Plain => self.normal(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dark_theme_matches_classic_colors() {
+        let dark = |role| match ThemeName::Dark.ink(role) {
+            Ink::Color(c) => Some(c),
+            _ => None,
+        };
+        assert_eq!(dark(Role::Accent), Some(Color::Cyan));
+        assert_eq!(dark(Role::Warning), Some(Color::Yellow));
+        assert!(matches!(ThemeName::Dark.ink(Role::Muted), Ink::Dim));
+    }
+
+    #[test]
+    fn light_theme_avoids_dim_and_yellow() {
+        for role in [Role::Muted, Role::Accent, Role::Warning] {
+            assert!(!matches!(
+                ThemeName::Light.ink(role),
+                Ink::Dim | Ink::Color(Color::Yellow | Color::Cyan)
+            ));
+        }
+    }
+
+    #[test]
+    fn mono_theme_is_plain() {
+        assert!(matches!(ThemeName::Mono.ink(Role::Error), Ink::Plain));
+    }
+
+    #[test]
+    fn only_explicit_color_modes_override_detection() {
+        assert_eq!(ColorMode::Auto.forced(), None);
+        assert_eq!(ColorMode::Always.forced(), Some(true));
+        assert_eq!(ColorMode::Never.forced(), Some(false));
+    }
+
+    #[test]
+    fn auto_color_follows_the_environment_then_the_terminal() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(ColorMode::Auto.colors(env(&[]), true));
+        assert!(!ColorMode::Auto.colors(env(&[]), false));
+        assert!(!ColorMode::Auto.colors(env(&[("NO_COLOR", "1")]), true));
+        assert!(!ColorMode::Auto.colors(env(&[("CLICOLOR", "0")]), true));
+        assert!(ColorMode::Auto.colors(env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]), false));
+        assert!(!ColorMode::Never.colors(env(&[("CLICOLOR_FORCE", "1")]), true));
+        assert!(ColorMode::Always.colors(env(&[("NO_COLOR", "1")]), false));
+    }
+
+    #[test]
+    fn theme_names_parse_case_insensitively() {
+        use clap::ValueEnum;
+        assert_eq!(ThemeName::from_str("LIGHT", true), Ok(ThemeName::Light));
+        assert!(ThemeName::from_str("solarized", true).is_err());
+    }
+}
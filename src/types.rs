@@ -25,6 +25,20 @@ pub struct OAuthToken {
     pub scopes: Vec<String>,
 }
 
+impl OAuthToken {
+    /// `expiresAt` as a timestamp. Claude Code writes milliseconds, but plain
+    /// seconds are accepted too.
+    pub fn expires_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        const MILLIS_THRESHOLD: u64 = 100_000_000_000;
+        let millis = if self.expires_at >= MILLIS_THRESHOLD {
+            self.expires_at
+        } else {
+            self.expires_at.checked_mul(1000)?
+        };
+        chrono::DateTime::from_timestamp_millis(i64::try_from(millis).ok()?)
+    }
+}
+
 impl std::fmt::Debug for OAuthToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OAuthToken")
@@ -38,21 +52,30 @@ impl std::fmt::Debug for OAuthToken {
 #[derive(Debug, Deserialize)]
 pub struct ProfileResponse {
     pub organization: OrgInfo,
+    pub account: Option<AccountInfo>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct OrgInfo {
     pub uuid: String,
+    pub name: Option<String>,
+}
+
+/// The user the access token was issued to.
+#[derive(Debug, Deserialize)]
+pub struct AccountInfo {
+    pub uuid: Option<String>,
+    pub display_name: Option<String>,
 }
 
 // ── Session ──────────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SessionsListResponse {
     pub data: Vec<Session>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct Session {
     pub id: String,
     pub title: Option<String>,
@@ -67,7 +90,29 @@ pub struct Session {
     pub active_mount_paths: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl Session {
+    /// True while the session is `running` or `idle`: more events may still
+    /// arrive, so anything fetched now is a partial snapshot.
+    pub fn is_live(&self) -> bool {
+        matches!(self.session_status.as_deref(), Some("running" | "idle"))
+    }
+}
+
+/// JSON keys of a serialized [`Session`], in declaration order.
+pub const SESSION_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "session_status",
+    "type",
+    "created_at",
+    "updated_at",
+    "environment_id",
+    "session_context",
+    "metadata",
+    "active_mount_paths",
+];
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct SessionContext {
     pub model: Option<String>,
     pub cwd: Option<String>,
@@ -78,7 +123,7 @@ pub struct SessionContext {
     pub knowledge_base_ids: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SessionSource {
     #[serde(rename = "type")]
     pub source_type: Option<String>,
@@ -86,14 +131,14 @@ pub struct SessionSource {
     pub revision: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SessionOutcome {
     #[serde(rename = "type")]
     pub outcome_type: Option<String>,
     pub git_info: Option<GitInfo>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GitInfo {
     #[serde(rename = "type")]
     pub git_type: Option<String>,
@@ -103,7 +148,7 @@ pub struct GitInfo {
 
 // ── Events ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EventsResponse {
     pub data: Vec<SessionEvent>,
     pub first_id: Option<String>,
@@ -113,7 +158,7 @@ pub struct EventsResponse {
 
 /// A tagged union over every event type the sessions API can return.
 /// Uses `#[serde(tag = "type")]` for internally-tagged deserialization.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SessionEvent {
     System(SystemEvent),
@@ -158,6 +203,19 @@ impl SessionEvent {
         }
     }
 
+    /// Event UUID; result and control_response events don't carry one.
+    pub fn uuid(&self) -> Option<&str> {
+        match self {
+            Self::System(e) => e.uuid.as_deref(),
+            Self::User(e) => e.uuid.as_deref(),
+            Self::Assistant(e) => e.uuid.as_deref(),
+            Self::ToolUseSummary(e) => e.uuid.as_deref(),
+            Self::ToolProgress(e) => e.uuid.as_deref(),
+            Self::EnvManagerLog(e) => e.uuid.as_deref(),
+            Self::Result(_) | Self::ControlResponse(_) | Self::Unknown => None,
+        }
+    }
+
     pub fn is_conversation(&self) -> bool {
         matches!(
             self,
@@ -166,7 +224,156 @@ impl SessionEvent {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+// ── Event schema table ───────────────────────────────────────────────
+
+/// A field of an event struct: Rust name, JSON key, and Rust type.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldInfo {
+    pub rust: &'static str,
+    pub json: &'static str,
+    pub ty: &'static str,
+}
+
+const fn field(rust: &'static str, json: &'static str, ty: &'static str) -> FieldInfo {
+    FieldInfo { rust, json, ty }
+}
+
+/// Describes one `SessionEvent` variant for the `describe` command.
+#[derive(Debug, Clone, Copy)]
+pub struct EventSchema {
+    /// Value of the JSON `type` tag, as returned by `event_type()`.
+    pub event_type: &'static str,
+    pub rust_type: &'static str,
+    pub fields: &'static [FieldInfo],
+}
+
+/// Static field table for every known event type. Keep in sync with the
+/// structs below; a test checks that each tag round-trips to its variant.
+pub const EVENT_SCHEMAS: &[EventSchema] = &[
+    EventSchema {
+        event_type: "system",
+        rust_type: "SystemEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("uuid", "uuid", "Option<String>"),
+            field("subtype", "subtype", "Option<String>"),
+            field("session_id", "session_id", "Option<String>"),
+            field("model", "model", "Option<String>"),
+            field("cwd", "cwd", "Option<String>"),
+            field(
+                "claude_code_version",
+                "claude_code_version",
+                "Option<String>",
+            ),
+            field("tools", "tools", "Option<Vec<String>>"),
+            field("agents", "agents", "Option<Vec<String>>"),
+            field("skills", "skills", "Option<Vec<String>>"),
+            field("slash_commands", "slash_commands", "Option<Vec<String>>"),
+            field("mcp_servers", "mcp_servers", "Option<Vec<Value>>"),
+            field("permission_mode", "permissionMode", "Option<String>"),
+            field("fast_mode_state", "fast_mode_state", "Option<String>"),
+            field("output_style", "output_style", "Option<String>"),
+        ],
+    },
+    EventSchema {
+        event_type: "user",
+        rust_type: "UserEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("uuid", "uuid", "Option<String>"),
+            field("session_id", "session_id", "Option<String>"),
+            field("message.role", "message.role", "Option<String>"),
+            field(
+                "message.content",
+                "message.content",
+                "UserContent (string or block array)",
+            ),
+            field("parent_tool_use_id", "parent_tool_use_id", "Option<String>"),
+            field("is_replay", "isReplay", "Option<bool>"),
+        ],
+    },
+    EventSchema {
+        event_type: "assistant",
+        rust_type: "AssistantEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("uuid", "uuid", "Option<String>"),
+            field("session_id", "session_id", "Option<String>"),
+            field("message.role", "message.role", "Option<String>"),
+            field("message.content", "message.content", "Vec<ContentBlock>"),
+        ],
+    },
+    EventSchema {
+        event_type: "tool_use_summary",
+        rust_type: "ToolUseSummaryEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("uuid", "uuid", "Option<String>"),
+            field("session_id", "session_id", "Option<String>"),
+            field("summary", "summary", "Option<String>"),
+            field(
+                "preceding_tool_use_ids",
+                "preceding_tool_use_ids",
+                "Option<Vec<String>>",
+            ),
+        ],
+    },
+    EventSchema {
+        event_type: "tool_progress",
+        rust_type: "ToolProgressEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("uuid", "uuid", "Option<String>"),
+            field("session_id", "session_id", "Option<String>"),
+            field("tool_name", "tool_name", "Option<String>"),
+            field("tool_use_id", "tool_use_id", "Option<String>"),
+            field("parent_tool_use_id", "parent_tool_use_id", "Option<String>"),
+            field(
+                "elapsed_time_seconds",
+                "elapsed_time_seconds",
+                "Option<u64>",
+            ),
+        ],
+    },
+    EventSchema {
+        event_type: "result",
+        rust_type: "ResultEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("duration_ms", "duration_ms", "Option<u64>"),
+            field("duration_api_ms", "duration_api_ms", "Option<u64>"),
+            field("errors", "errors", "Option<Vec<String>>"),
+        ],
+    },
+    EventSchema {
+        event_type: "control_response",
+        rust_type: "ControlResponseEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("response.subtype", "response.subtype", "Option<String>"),
+        ],
+    },
+    EventSchema {
+        event_type: "env_manager_log",
+        rust_type: "EnvManagerLogEvent",
+        fields: &[
+            field("created_at", "created_at", "Option<String>"),
+            field("uuid", "uuid", "Option<String>"),
+            field("data.category", "data.category", "Option<String>"),
+            field("data.content", "data.content", "Option<String>"),
+            field("data.level", "data.level", "Option<String>"),
+            field("data.timestamp", "data.timestamp", "Option<String>"),
+            field("data.extra", "data.extra", "Option<Value>"),
+        ],
+    },
+];
+
+/// Looks up the schema for an event type tag such as `"tool_progress"`.
+pub fn event_schema(event_type: &str) -> Option<&'static EventSchema> {
+    EVENT_SCHEMAS.iter().find(|s| s.event_type == event_type)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SystemEvent {
     pub created_at: Option<String>,
     pub uuid: Option<String>,
@@ -186,7 +393,56 @@ pub struct SystemEvent {
     pub output_style: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl SystemEvent {
+    /// Names of the MCP servers, which arrive either as plain strings or as
+    /// `{"name": ..., "status": ...}` objects.
+    pub fn mcp_server_names(&self) -> Vec<&str> {
+        self.mcp_servers
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| v.as_str().or_else(|| v.get("name")?.as_str()))
+            .collect()
+    }
+
+    /// One line per MCP server: its name, then `[status]` and `: command args`
+    /// when present. Entries without a name show as a compact JSON preview.
+    pub fn mcp_server_descriptions(&self) -> Vec<String> {
+        const PREVIEW_CHARS: usize = 80;
+        self.mcp_servers
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|v| {
+                if let Some(name) = v.as_str() {
+                    return name.to_string();
+                }
+                let Some(name) = v.get("name").and_then(|n| n.as_str()) else {
+                    let json = v.to_string();
+                    return match json.char_indices().nth(PREVIEW_CHARS) {
+                        Some((cut, _)) => format!("{}...", &json[..cut]),
+                        None => json,
+                    };
+                };
+                let mut line = name.to_string();
+                if let Some(status) = v.get("status").and_then(|s| s.as_str()) {
+                    line.push_str(&format!(" [{status}]"));
+                }
+                if let Some(command) = v.get("command").and_then(|c| c.as_str()) {
+                    line.push_str(&format!(": {command}"));
+                    let args = v.get("args").and_then(|a| a.as_array());
+                    for arg in args.into_iter().flatten().filter_map(|a| a.as_str()) {
+                        line.push(' ');
+                        line.push_str(arg);
+                    }
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserEvent {
     pub created_at: Option<String>,
     pub uuid: Option<String>,
@@ -197,14 +453,14 @@ pub struct UserEvent {
     pub is_replay: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserMessage {
     pub role: Option<String>,
     pub content: UserContent,
 }
 
 /// User content can be a plain string or a list of content blocks.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum UserContent {
     Text(String),
@@ -218,9 +474,22 @@ impl UserContent {
             Self::Blocks(_) => None,
         }
     }
+
+    /// All plain text in the message: the string itself, or the `text` of
+    /// every `{"type": "text"}` block.
+    pub fn texts(&self) -> Vec<&str> {
+        match self {
+            Self::Text(s) => vec![s.as_str()],
+            Self::Blocks(blocks) => blocks
+                .iter()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+                .filter_map(|b| b.get("text")?.as_str())
+                .collect(),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AssistantEvent {
     pub created_at: Option<String>,
     pub uuid: Option<String>,
@@ -228,14 +497,14 @@ pub struct AssistantEvent {
     pub message: AssistantMessage,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AssistantMessage {
     pub role: Option<String>,
     pub content: Vec<ContentBlock>,
 }
 
 /// A content block in an assistant message.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlock {
     Thinking(ThinkingBlock),
@@ -247,32 +516,32 @@ pub enum ContentBlock {
     Other,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ThinkingBlock {
     pub thinking: Option<String>,
     pub signature: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TextBlock {
     pub text: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ToolUseBlock {
     pub id: Option<String>,
     pub name: Option<String>,
     pub input: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ToolResultBlock {
     pub tool_use_id: Option<String>,
     pub content: Option<serde_json::Value>,
     pub is_error: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ToolUseSummaryEvent {
     pub created_at: Option<String>,
     pub uuid: Option<String>,
@@ -281,7 +550,7 @@ pub struct ToolUseSummaryEvent {
     pub preceding_tool_use_ids: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ToolProgressEvent {
     pub created_at: Option<String>,
     pub uuid: Option<String>,
@@ -292,7 +561,7 @@ pub struct ToolProgressEvent {
     pub elapsed_time_seconds: Option<u64>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ResultEvent {
     pub created_at: Option<String>,
     pub duration_ms: Option<u64>,
@@ -300,25 +569,25 @@ pub struct ResultEvent {
     pub errors: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ControlResponseEvent {
     pub created_at: Option<String>,
     pub response: Option<ControlResponseData>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ControlResponseData {
     pub subtype: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EnvManagerLogEvent {
     pub created_at: Option<String>,
     pub uuid: Option<String>,
     pub data: Option<EnvManagerLogData>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EnvManagerLogData {
     pub category: Option<String>,
     pub content: Option<String>,
@@ -327,6 +596,17 @@ pub struct EnvManagerLogData {
     pub extra: Option<serde_json::Value>,
 }
 
+// ── Export file ──────────────────────────────────────────────────────
+
+/// The JSON document written by `export` and read back by `--from-file`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionExport {
+    pub session: Session,
+    pub events: Vec<SessionEvent>,
+    pub exported_at: Option<String>,
+    pub total_events: Option<usize>,
+}
+
 // ── Loglines (session_ingress) ───────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -334,7 +614,7 @@ pub struct IngressResponse {
     pub loglines: Vec<Logline>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Logline {
     #[serde(rename = "type")]
     pub log_type: Option<String>,
@@ -384,6 +664,28 @@ mod tests {
         assert_eq!(creds.claude_ai_oauth.scopes, vec!["read", "write"]);
     }
 
+    #[test]
+    fn oauth_token_expiry_accepts_seconds_and_millis() {
+        let token = |expires_at| OAuthToken {
+            access_token: String::new(),
+            expires_at,
+            scopes: Vec::new(),
+        };
+        let expected = "2023-11-14T22:13:20+00:00";
+        assert_eq!(
+            token(1_700_000_000).expires_at_utc().unwrap().to_rfc3339(),
+            expected
+        );
+        assert_eq!(
+            token(1_700_000_000_000)
+                .expires_at_utc()
+                .unwrap()
+                .to_rfc3339(),
+            expected
+        );
+        assert!(token(u64::MAX).expires_at_utc().is_none());
+    }
+
     #[test]
     fn deserialize_oauth_credentials_ignores_extra_fields() {
         let json = json!({
@@ -425,6 +727,17 @@ mod tests {
         });
         let profile: ProfileResponse = serde_json::from_value(json).unwrap();
         assert_eq!(profile.organization.uuid, "org-123");
+        let account = profile.account.unwrap();
+        assert_eq!(account.uuid.as_deref(), Some("acc-456"));
+        assert_eq!(account.display_name.as_deref(), Some("Test"));
+    }
+
+    #[test]
+    fn profile_response_without_account() {
+        let json = json!({ "organization": { "uuid": "org-1" } });
+        let profile: ProfileResponse = serde_json::from_value(json).unwrap();
+        assert!(profile.account.is_none());
+        assert!(profile.organization.name.is_none());
     }
 
     #[test]
@@ -509,6 +822,17 @@ mod tests {
         assert_eq!(resp.data[1].title.as_deref(), Some("Second"));
     }
 
+    #[test]
+    fn session_is_live_only_while_running_or_idle() {
+        let with_status = |status: Option<&str>| -> Session {
+            serde_json::from_value(json!({ "id": "s1", "session_status": status })).unwrap()
+        };
+        assert!(with_status(Some("running")).is_live());
+        assert!(with_status(Some("idle")).is_live());
+        assert!(!with_status(Some("completed")).is_live());
+        assert!(!with_status(None).is_live());
+    }
+
     // ── SessionEvent deserialization ────────────────────────────────
 
     #[test]
@@ -746,6 +1070,29 @@ mod tests {
         assert!(!event.is_conversation());
     }
 
+    // ── EVENT_SCHEMAS ───────────────────────────────────────────────
+
+    #[test]
+    fn event_schemas_match_variants() {
+        for schema in EVENT_SCHEMAS {
+            let event: SessionEvent = serde_json::from_value(
+                json!({ "type": schema.event_type, "message": { "content": [] } }),
+            )
+            .unwrap();
+            assert_eq!(event.event_type(), schema.event_type);
+            assert!(!schema.fields.is_empty());
+        }
+        assert_eq!(EVENT_SCHEMAS.len(), 8);
+    }
+
+    #[test]
+    fn event_schema_lookup() {
+        let schema = event_schema("user").unwrap();
+        assert_eq!(schema.rust_type, "UserEvent");
+        assert!(schema.fields.iter().any(|f| f.json == "isReplay"));
+        assert!(event_schema("nope").is_none());
+    }
+
     // ── EventsResponse ──────────────────────────────────────────────
 
     #[test]
@@ -809,6 +1156,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn system_event_mcp_server_names() {
+        let json = json!({
+            "type": "system",
+            "subtype": "init",
+            "mcp_servers": [
+                { "name": "github", "status": "connected" },
+                "linear",
+                { "status": "failed" }
+            ]
+        });
+        let SessionEvent::System(e) = serde_json::from_value(json).unwrap() else {
+            panic!("expected system event");
+        };
+        assert_eq!(e.mcp_server_names(), vec!["github", "linear"]);
+    }
+
+    #[test]
+    fn system_event_mcp_server_descriptions() {
+        let json = json!({
+            "type": "system",
+            "mcp_servers": [
+                { "name": "github", "status": "connected" },
+                { "name": "fs", "command": "npx", "args": ["-y", "@mcp/fs", 3] },
+                "linear",
+                { "transport": "sse", "url": "https://mcp.example.com" }
+            ]
+        });
+        let SessionEvent::System(e) = serde_json::from_value(json).unwrap() else {
+            panic!("expected system event");
+        };
+        assert_eq!(
+            e.mcp_server_descriptions(),
+            vec![
+                "github [connected]",
+                "fs: npx -y @mcp/fs",
+                "linear",
+                r#"{"transport":"sse","url":"https://mcp.example.com"}"#
+            ]
+        );
+    }
+
     // ── UserContent ─────────────────────────────────────────────────
 
     #[test]
@@ -825,6 +1214,21 @@ mod tests {
         assert!(content.as_text().is_none());
     }
 
+    #[test]
+    fn user_content_texts_from_blocks() {
+        let json = json!([
+            { "type": "text", "text": "first" },
+            { "type": "image", "source": { "type": "base64" } },
+            { "type": "tool_result", "tool_use_id": "tu_1", "content": "out" },
+            { "type": "text", "text": "second" }
+        ]);
+        let content: UserContent = serde_json::from_value(json).unwrap();
+        assert_eq!(content.texts(), vec!["first", "second"]);
+
+        let plain = UserContent::Text("only".to_string());
+        assert_eq!(plain.texts(), vec!["only"]);
+    }
+
     // ── Logline ─────────────────────────────────────────────────────
 
     #[test]
@@ -892,6 +1296,7 @@ mod tests {
         let deserialized: SessionEvent = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized.event_type(), "user");
         assert_eq!(deserialized.created_at(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(deserialized.uuid(), Some("evt_123"));
     }
 
     #[test]
@@ -914,6 +1319,33 @@ mod tests {
         assert_eq!(deserialized.title.as_deref(), Some("Test Session"));
     }
 
+    // ── SessionExport ───────────────────────────────────────────────
+
+    #[test]
+    fn deserialize_session_export() {
+        let json = json!({
+            "session": { "id": "session_01export", "title": "Exported" },
+            "events": [
+                { "type": "user", "message": { "content": "hi" } },
+                { "type": "result", "duration_ms": 1000 }
+            ],
+            "exported_at": "2025-01-01T00:00:00+00:00",
+            "total_events": 2
+        });
+        let export: SessionExport = serde_json::from_value(json).unwrap();
+        assert_eq!(export.session.id, "session_01export");
+        assert_eq!(export.events.len(), 2);
+        assert_eq!(export.total_events, Some(2));
+    }
+
+    #[test]
+    fn deserialize_session_export_without_extras() {
+        let json = json!({ "session": { "id": "s1" }, "events": [] });
+        let export: SessionExport = serde_json::from_value(json).unwrap();
+        assert!(export.events.is_empty());
+        assert!(export.exported_at.is_none());
+    }
+
     // ── Mixed event list (like real API responses) ──────────────────
 
     #[test]
@@ -0,0 +1,466 @@
+//! `ApiClient` against a local wiremock server standing in for the API.
+
+use claude_teleport_analyzer::{ApiClient, ApiError, EventQuery};
+use serde_json::json;
+use std::ops::ControlFlow;
+use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TOKEN: &str = "tok_test";
+const ORG_UUID: &str = "org-1234";
+const SESSION_ID: &str = "session_01MockSession0000";
+
+/// Starts a server whose profile endpoint resolves `TOKEN` to `ORG_UUID`.
+async fn server_with_profile() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/oauth/profile"))
+        .and(header("authorization", "Bearer tok_test"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "organization": { "uuid": ORG_UUID } })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn connect(server: &MockServer) -> ApiClient {
+    ApiClient::connect(&server.uri(), TOKEN).await.unwrap()
+}
+
+fn event_page(ids: &[&str], has_more: bool) -> serde_json::Value {
+    let data: Vec<_> = ids
+        .iter()
+        .map(|id| json!({ "type": "user", "uuid": id, "message": { "content": *id } }))
+        .collect();
+    json!({
+        "data": data,
+        "first_id": ids.first(),
+        "last_id": ids.last(),
+        "has_more": has_more,
+    })
+}
+
+// ── Headers ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn list_sessions_sends_auth_and_org_headers() {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/sessions"))
+        .and(header("authorization", "Bearer tok_test"))
+        .and(header("x-organization-uuid", ORG_UUID))
+        .and(header("anthropic-beta", "ccr-byoc-2025-07-29"))
+        .and(header("anthropic-version", "2023-06-01"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [
+                { "id": SESSION_ID, "title": "First", "session_status": "idle" },
+                { "id": "session_01OtherSession000" }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let sessions = connect(&server).await.list_sessions().await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].title.as_deref(), Some("First"));
+}
+
+#[tokio::test]
+async fn get_session_parses_metadata() {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/sessions/{SESSION_ID}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": SESSION_ID,
+            "session_status": "completed",
+            "session_context": { "model": "claude-opus-4-20250514" }
+        })))
+        .mount(&server)
+        .await;
+
+    let session = connect(&server)
+        .await
+        .get_session(SESSION_ID)
+        .await
+        .unwrap();
+    assert_eq!(session.id, SESSION_ID);
+    assert_eq!(
+        session.session_context.unwrap().model.as_deref(),
+        Some("claude-opus-4-20250514")
+    );
+}
+
+#[tokio::test]
+async fn get_profile_returns_org_and_account() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/oauth/profile"))
+        .and(header("authorization", "Bearer tok_test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "organization": { "uuid": ORG_UUID, "name": "Acme" },
+            "account": { "uuid": "acct-1", "display_name": "Ada" }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let profile = connect(&server).await.get_profile().await.unwrap();
+    assert_eq!(profile.organization.uuid, ORG_UUID);
+    assert_eq!(profile.organization.name.as_deref(), Some("Acme"));
+    assert_eq!(
+        profile.account.unwrap().display_name.as_deref(),
+        Some("Ada")
+    );
+}
+
+// ── Pagination ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn get_events_follows_last_id_until_has_more_is_false() {
+    let server = server_with_profile().await;
+    let events_path = format!("/v1/sessions/{SESSION_ID}/events");
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param_is_missing("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1", "e2"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e3"], false)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let events = connect(&server)
+        .await
+        .get_events(SESSION_ID, 0)
+        .await
+        .unwrap();
+    let texts: Vec<_> = events
+        .iter()
+        .map(|e| match e {
+            claude_teleport_analyzer::SessionEvent::User(u) => u.message.content.texts()[0],
+            _ => panic!("expected user event"),
+        })
+        .collect();
+    assert_eq!(texts, vec!["e1", "e2", "e3"]);
+}
+
+#[tokio::test]
+async fn get_events_stops_at_max_events() {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/sessions/{SESSION_ID}/events")))
+        .and(query_param_is_missing("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1", "e2"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let events = connect(&server)
+        .await
+        .get_events(SESSION_ID, 1)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+}
+
+#[tokio::test]
+async fn get_events_stops_after_max_pages() {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/sessions/{SESSION_ID}/events")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1"], true)))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let events = connect(&server)
+        .await
+        .with_max_pages(3)
+        .get_events(SESSION_ID, 0)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 3);
+}
+
+#[tokio::test]
+async fn page_size_is_sent_as_limit_on_every_page() {
+    let server = server_with_profile().await;
+    let events_path = format!("/v1/sessions/{SESSION_ID}/events");
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("limit", "2"))
+        .and(query_param_is_missing("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1", "e2"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("limit", "2"))
+        .and(query_param("after_id", "e2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e3"], false)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let events = connect(&server)
+        .await
+        .with_page_size(2)
+        .get_events(SESSION_ID, 0)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 3);
+}
+
+#[tokio::test]
+async fn find_event_stops_paging_once_found() {
+    let server = server_with_profile().await;
+    let events_path = format!("/v1/sessions/{SESSION_ID}/events");
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param_is_missing("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1", "e2"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e3", "e4"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e5"], false)))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let api = connect(&server).await;
+    let event = api.find_event(SESSION_ID, "e3").await.unwrap().unwrap();
+    assert_eq!(event.uuid(), Some("e3"));
+}
+
+#[tokio::test]
+async fn find_event_returns_none_when_missing() {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/sessions/{SESSION_ID}/events")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1"], false)))
+        .mount(&server)
+        .await;
+
+    let api = connect(&server).await;
+    assert!(api.find_event(SESSION_ID, "nope").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn get_events_query_seeds_cursor_and_stops_at_before_id() {
+    let server = server_with_profile().await;
+    let events_path = format!("/v1/sessions/{SESSION_ID}/events");
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e3", "e4"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e5", "e6"], true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let query = EventQuery {
+        after_id: Some("e2".to_string()),
+        before_id: Some("e6".to_string()),
+        ..Default::default()
+    };
+    let events = connect(&server)
+        .await
+        .get_events_query(SESSION_ID, &query)
+        .await
+        .unwrap();
+    let uuids: Vec<_> = events.iter().filter_map(|e| e.uuid()).collect();
+    assert_eq!(uuids, vec!["e3", "e4", "e5"]);
+}
+
+#[tokio::test]
+async fn best_effort_returns_pages_fetched_before_a_failure() {
+    let server = server_with_profile().await;
+    let events_path = format!("/v1/sessions/{SESSION_ID}/events");
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param_is_missing("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1", "e2"], true)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e2"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("backend hiccup"))
+        .mount(&server)
+        .await;
+    let client = connect(&server).await;
+
+    let strict = client.get_events(SESSION_ID, 0).await.unwrap_err();
+    assert!(matches!(
+        strict.downcast_ref::<ApiError>(),
+        Some(ApiError::Server { .. })
+    ));
+    assert!(strict.to_string().contains("(page 2)"));
+
+    let query = EventQuery {
+        best_effort: true,
+        ..Default::default()
+    };
+    let events = client.get_events_query(SESSION_ID, &query).await.unwrap();
+    assert_eq!(events.len(), 2);
+}
+
+#[tokio::test]
+async fn best_effort_still_fails_on_first_page() {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/sessions/{SESSION_ID}/events")))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let query = EventQuery {
+        best_effort: true,
+        ..Default::default()
+    };
+    let result = connect(&server)
+        .await
+        .get_events_query(SESSION_ID, &query)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn for_each_event_streams_pages_and_stops_on_break_or_error() {
+    let server = server_with_profile().await;
+    let events_path = format!("/v1/sessions/{SESSION_ID}/events");
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param_is_missing("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e1", "e2"], true)))
+        .expect(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(events_path.as_str()))
+        .and(query_param("after_id", "e2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(event_page(&["e3"], false)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = connect(&server).await;
+
+    let mut seen = Vec::new();
+    let count = client
+        .for_each_event(SESSION_ID, &EventQuery::default(), |event| {
+            seen.push(event.uuid().unwrap().to_string());
+            Ok(ControlFlow::Continue(()))
+        })
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(seen, vec!["e1", "e2", "e3"]);
+
+    let mut seen = Vec::new();
+    let count = client
+        .for_each_event(SESSION_ID, &EventQuery::default(), |event| {
+            seen.push(event.uuid().unwrap().to_string());
+            Ok(ControlFlow::Break(()))
+        })
+        .await
+        .unwrap();
+    assert_eq!(count, 1, "breaking stops before the next event and page");
+    assert_eq!(seen, vec!["e1"]);
+
+    let err = client
+        .for_each_event(SESSION_ID, &EventQuery::default(), |event| {
+            anyhow::ensure!(event.uuid() != Some("e2"), "disk full");
+            Ok(ControlFlow::Continue(()))
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "disk full");
+}
+
+// ── Error mapping ────────────────────────────────────────────────────
+
+async fn list_sessions_error(status: u16) -> anyhow::Error {
+    let server = server_with_profile().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/sessions"))
+        .respond_with(ResponseTemplate::new(status).set_body_string("upstream says no"))
+        .mount(&server)
+        .await;
+    connect(&server).await.list_sessions().await.unwrap_err()
+}
+
+#[tokio::test]
+async fn error_statuses_map_to_api_error_variants() {
+    for (status, expected) in [
+        (401, "Unauthorized"),
+        (404, "NotFound"),
+        (429, "RateLimited"),
+        (503, "Server"),
+        (400, "Other"),
+    ] {
+        let err = list_sessions_error(status).await;
+        let api_err = err.downcast_ref::<ApiError>().expect("ApiError");
+        let variant = match api_err {
+            ApiError::Unauthorized { .. } => "Unauthorized",
+            ApiError::NotFound { .. } => "NotFound",
+            ApiError::RateLimited { .. } => "RateLimited",
+            ApiError::Server { .. } => "Server",
+            ApiError::Other { .. } => "Other",
+        };
+        assert_eq!(variant, expected, "status {status}");
+    }
+}
+
+#[tokio::test]
+async fn error_message_includes_context_and_body() {
+    let err = list_sessions_error(400).await;
+    assert_eq!(
+        err.to_string(),
+        "Failed to list sessions: 400 Bad Request - upstream says no"
+    );
+}
+
+#[tokio::test]
+async fn connect_fails_when_profile_rejects_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/oauth/profile"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let err = ApiClient::connect(&server.uri(), TOKEN)
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.downcast_ref::<ApiError>(),
+        Some(ApiError::Unauthorized { .. })
+    ));
+}